@@ -1,3 +1,5 @@
 pub mod util;
+pub mod registry;
 pub mod solutions;
 pub mod optimized;
+pub mod runner;
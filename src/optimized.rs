@@ -1,2 +1,4 @@
-pub mod day01;
-pub mod day06;
+crate::registry::register! {
+    1 => day01,
+    6 => day06,
+}
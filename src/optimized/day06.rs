@@ -1,31 +1,38 @@
-use crate::util::parsers::Numbers;
-use itertools::Itertools;
+use crate::{solutions::day06::parse, util::parsers::ParseError};
 
-pub fn run(input: &str) -> (u64, u64) {
-    // Parse
-    let (times, dists) = input.split_once('\n').unwrap();
-    let times = Numbers::<u64>::new(times);
-    let dists = Numbers::<u64>::new(dists);
+pub fn run(input: &str) -> Result<(u64, u64), ParseError> {
+    let (races, (time, dist)) = parse(input)?;
 
     // Part 1
     let mut part1 = 1;
-    for (time, dist) in times.zip(dists) {
+    for (time, dist) in races {
         part1 *= ways_to_beat(time, dist);
     }
 
     // Part 2
-    let input: String = input
-        .chars()
-        .filter(|x| x.is_ascii_digit() || *x == '\n')
-        .collect();
-    let (time, dist): (u64, u64) = Numbers::new(&input).collect_tuple().unwrap();
     let part2 = ways_to_beat(time, dist);
 
-    (part1, part2)
+    Ok((part1, part2))
 }
 
+/// Solves `hold * (time - hold) > dist` for `hold` using the quadratic formula.
+/// The lower root is corrected with integer arithmetic in case of rounding errors.
 #[inline(always)]
 fn ways_to_beat(time: u64, dist: u64) -> u64 {
     let (t, d) = (time as f64, dist as f64);
-    (t.powi(2) - 4.0 * d).sqrt().ceil() as u64 + 1
+    let discriminant = t.powi(2) - 4.0 * d;
+    if discriminant < 0.0 {
+        return 0;
+    }
+    let mut low = ((t - discriminant.sqrt()) / 2.0).floor() as u64;
+    while low <= time / 2 && low * (time - low) <= dist {
+        low += 1;
+    }
+    while low > 0 && (low - 1) * (time - low + 1) > dist {
+        low -= 1;
+    }
+    if low > time / 2 {
+        return 0;
+    }
+    time + 1 - 2 * low
 }
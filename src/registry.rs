@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+
+use crate::{
+    optimized, solutions,
+    util::{answer::Answer, parsers::ParseError},
+};
+
+/// A registered solution for a single day.
+pub struct Solution {
+    pub day: usize,
+    /// Path of the module that the solution was registered in, fx. `aoc2023::optimized`.
+    pub module: &'static str,
+    /// Runs both parts on an input.
+    pub run: fn(&str) -> Result<(Answer, Answer), ParseError>,
+    /// Runs both parts on an input, timing each phase.
+    /// `None` for solutions with a single `run` function.
+    pub run_phases: Option<PhasedRun>,
+}
+
+/// Runs both parts of a solution on an input, timing each phase.
+pub type PhasedRun = fn(&str) -> Result<((Answer, Answer), PhaseTimes), ParseError>;
+
+/// Return types of `run` functions: both answers, or both answers unless the input is invalid.
+pub trait IntoAnswers {
+    fn into_answers(self) -> Result<(Answer, Answer), ParseError>;
+}
+
+impl<A: Into<Answer>, B: Into<Answer>> IntoAnswers for (A, B) {
+    fn into_answers(self) -> Result<(Answer, Answer), ParseError> {
+        Ok((self.0.into(), self.1.into()))
+    }
+}
+
+impl<A: Into<Answer>, B: Into<Answer>> IntoAnswers for Result<(A, B), ParseError> {
+    fn into_answers(self) -> Result<(Answer, Answer), ParseError> {
+        self.and_then(IntoAnswers::into_answers)
+    }
+}
+
+/// Time spent in each phase of a solution that is split into `parse`, `part1` and `part2`.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct PhaseTimes {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+impl PhaseTimes {
+    /// Average time of each phase over several runs.
+    pub fn mean(runs: &[PhaseTimes]) -> Self {
+        let n = runs.len() as u32;
+        Self {
+            parse: runs.iter().map(|t| t.parse).sum::<Duration>() / n,
+            part1: runs.iter().map(|t| t.part1).sum::<Duration>() / n,
+            part2: runs.iter().map(|t| t.part2).sum::<Duration>() / n,
+        }
+    }
+}
+
+/// Runs `parse`, `part1` and `part2` in order, timing each of them.
+pub fn run_phases<P, A: Into<Answer>, B: Into<Answer>>(
+    input: &str,
+    parse: impl FnOnce(&str) -> Result<P, ParseError>,
+    part1: impl FnOnce(&P) -> A,
+    part2: impl FnOnce(&P) -> B,
+) -> Result<((Answer, Answer), PhaseTimes), ParseError> {
+    let start = Instant::now();
+    let parsed = parse(input)?;
+    let parsed_at = Instant::now();
+    let a = part1(&parsed).into();
+    let part1_at = Instant::now();
+    let b = part2(&parsed).into();
+    let end = Instant::now();
+    let times = PhaseTimes {
+        parse: parsed_at - start,
+        part1: part1_at - parsed_at,
+        part2: end - part1_at,
+    };
+    Ok(((a, b), times))
+}
+
+impl Solution {
+    /// Name of the variant that this solution belongs to, fx. `solutions` or `optimized`.
+    pub fn variant(&self) -> &'static str {
+        self.module.rsplit("::").next().unwrap()
+    }
+}
+
+/// Declares solution modules and registers their `run` functions in a `REGISTRY` constant.
+///
+/// Each entry `day => module` expands to `pub mod module;`,
+/// so adding a day is a single line in the variant's module list.
+/// Modules are expected to contain `run(&str) -> (A, B)` or `run(&str) -> Result<(A, B), ParseError>`,
+/// or `parse`, `part1` and `part2` functions when marked with `(phased)`,
+/// where `parse` returns a `Result<_, ParseError>`.
+macro_rules! register {
+    ($($day:literal => $module:ident $(($kind:ident))?,)*) => {
+        $(pub mod $module;)*
+
+        pub const REGISTRY: &[$crate::registry::Solution] = &[$(
+            $crate::registry::solution!($day, $module $(, $kind)?),
+        )*];
+    };
+}
+pub(crate) use register;
+
+macro_rules! solution {
+    ($day:literal, $module:ident) => {
+        $crate::registry::Solution {
+            day: $day,
+            module: module_path!(),
+            run: |input| $crate::registry::IntoAnswers::into_answers($module::run(input)),
+            run_phases: None,
+        }
+    };
+    ($day:literal, $module:ident, phased) => {
+        $crate::registry::Solution {
+            day: $day,
+            module: module_path!(),
+            run: |input| {
+                let parsed = $module::parse(input)?;
+                Ok(($module::part1(&parsed).into(), $module::part2(&parsed).into()))
+            },
+            run_phases: Some(|input| {
+                $crate::registry::run_phases(
+                    input,
+                    $module::parse,
+                    |parsed| $module::part1(parsed),
+                    |parsed| $module::part2(parsed),
+                )
+            }),
+        }
+    };
+}
+pub(crate) use solution;
+
+/// All registered solutions, ordered by day.
+/// Variants of the same day are ordered `solutions` first, then `optimized`.
+pub fn all() -> Vec<&'static Solution> {
+    solutions::REGISTRY
+        .iter()
+        .chain(optimized::REGISTRY)
+        .sorted_by_key(|solution| solution.day)
+        .collect()
+}
+
+/// Names of all variants, `solutions` first.
+pub fn variants() -> Vec<&'static str> {
+    all().iter().map(|solution| solution.variant()).unique().collect()
+}
+
+/// All registered solutions, grouped by day.
+pub fn by_day() -> Vec<(usize, Vec<&'static Solution>)> {
+    all()
+        .into_iter()
+        .group_by(|solution| solution.day)
+        .into_iter()
+        .map(|(day, solutions)| (day, solutions.collect()))
+        .collect()
+}
@@ -0,0 +1,778 @@
+use std::fs::read_to_string;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use comfy_table::{
+    presets::{ASCII_MARKDOWN, UTF8_FULL_CONDENSED},
+    Cell, CellAlignment, Color, Table,
+};
+
+use crate::registry::{self, PhaseTimes, Solution};
+use crate::util::answer::Answer;
+use crate::util::profiles::{self, Profile};
+
+pub mod alloc;
+pub mod args;
+pub mod baseline;
+pub mod bench;
+pub mod isolate;
+pub mod output;
+pub mod pool;
+pub mod scaffold;
+pub mod verify;
+pub mod watch;
+
+use alloc::AllocStats;
+use args::{Args, Input};
+use baseline::Baseline;
+use bench::Stats;
+use isolate::Failure;
+use output::{Format, Record};
+use verify::Verdict;
+
+pub fn main(args: Args) -> Result<(), String> {
+    if let Some(day) = args.new_day {
+        for path in scaffold::new_day(Path::new("."), day)? {
+            println!("{}", path.display());
+        }
+        return Ok(());
+    }
+    if args.watch {
+        return watch::watch(&args);
+    }
+    let mut states = Vec::new();
+    if let Some(input) = &args.input {
+        let days = (1..=25).filter(|day| args.has_day(*day)).count();
+        if days != 1 {
+            return Err("`--input` requires selecting a single day with `--day`".to_owned());
+        }
+        let (name, text) = match input {
+            Input::File(path) => {
+                let text = read_to_string(path)
+                    .map_err(|e| format!("couldn't read {}: {e}", path.display()))?;
+                (path.display().to_string(), text)
+            }
+            Input::Stdin => {
+                let mut text = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut text)
+                    .map_err(|e| format!("couldn't read stdin: {e}"))?;
+                ("stdin".to_owned(), text)
+            }
+        };
+        states.push(State::new(&args, name, Source::Text(text))?);
+    } else {
+        for profile in profiles::all().map_err(|e| e.to_string())? {
+            if args.has_profile(&profile.name) {
+                let name = profile.name.clone();
+                states.push(State::new(&args, name, Source::Profile(profile))?);
+            }
+        }
+        if states.is_empty() {
+            return Err(format!("no matching profiles in {}", profiles::DATA_DIR));
+        }
+    }
+
+    if args.alloc {
+        alloc::enable();
+    }
+
+    // Benchmarks run serially, so that they don't compete for the CPU.
+    let jobs = match args.bench {
+        Some(_) if args.jobs > 1 => {
+            eprintln!("note: ignoring `--jobs` in benchmark mode");
+            1
+        }
+        _ => args.jobs,
+    };
+    let by_day = registry::by_day();
+    let mut tasks = Vec::new();
+    for idx in 0..states.len() {
+        for (day, solutions) in &by_day {
+            if args.has_day(*day) {
+                tasks.push((idx, *day, solutions.as_slice()));
+            }
+        }
+    }
+    let results = pool::map(jobs, &tasks, |&(idx, day, solutions)| {
+        states[idx].run_day(day, solutions)
+    });
+    for (&(idx, ..), day_runs) in tasks.iter().zip(results) {
+        states[idx].days.push(day_runs?);
+    }
+
+    let mut records = Vec::new();
+    let mut wrong = 0;
+    for state in states {
+        wrong += state.print(&mut records)?;
+    }
+    print_records(&args, &records);
+    match wrong {
+        0 => Ok(()),
+        1 => Err("1 wrong answer".to_owned()),
+        n => Err(format!("{n} wrong answers")),
+    }
+}
+
+/// Prints the results of all profiles at once, for structured formats.
+fn print_records(args: &Args, records: &[Record]) {
+    match args.format {
+        Format::Json => println!("{}", output::json(records)),
+        Format::Csv => print!("{}", output::csv(records)),
+        Format::Table | Format::Markdown => {}
+    }
+}
+
+/// Where the inputs for a run come from.
+enum Source {
+    /// A profile folder with inputs in `inputs/day01.txt`, `inputs/day02.txt`, etc.
+    Profile(Profile),
+    /// A single input, used for whichever day is selected.
+    Text(String),
+}
+
+/// The result of running a single variant of a day.
+struct Run {
+    variant: &'static str,
+    result: Result<Finished, Failure>,
+}
+
+/// Answers and timings of a solution that finished successfully.
+struct Finished {
+    answers: [Answer; 2],
+    stats: Stats,
+    /// Average time of each phase, for solutions that are split into phases.
+    phases: Option<PhaseTimes>,
+    /// Allocations of the first run, when counting them.
+    allocs: Option<AllocStats>,
+}
+
+/// All of the runs of a single day, on the same input.
+struct DayRuns {
+    day: usize,
+    /// Known correct answers, if the profile has them.
+    expected: [Option<Answer>; 2],
+    runs: Vec<Run>,
+}
+
+impl DayRuns {
+    /// The answers of the first variant that finished, which the others are compared against.
+    fn reference(&self) -> Option<&Finished> {
+        self.runs.iter().find_map(|run| run.result.as_ref().ok())
+    }
+
+    fn finished(&self) -> impl Iterator<Item = (&'static str, &Finished)> {
+        self.runs
+            .iter()
+            .filter_map(|run| Some((run.variant, run.result.as_ref().ok()?)))
+    }
+
+    fn verdict(&self, part: usize, answer: &Answer) -> Verdict {
+        Verdict::check(self.expected[part - 1].as_ref(), answer)
+    }
+}
+
+struct State<'a> {
+    args: &'a Args,
+    /// Name of the profile, or of the input file.
+    name: String,
+    source: Source,
+    variants: Vec<&'static str>,
+    days: Vec<DayRuns>,
+    /// Benchmark results from earlier runs of the same profile.
+    baseline: Baseline,
+}
+
+impl<'a> State<'a> {
+    fn new(args: &'a Args, name: String, source: Source) -> Result<Self, String> {
+        let mut state = Self {
+            args,
+            name,
+            source,
+            variants: registry::variants(),
+            days: Vec::new(),
+            baseline: Baseline::default(),
+        };
+        if let Some(path) = state.baseline_path() {
+            state.baseline = Baseline::load(&path)?;
+        }
+        Ok(state)
+    }
+
+    /// Location of the benchmark baseline, only relevant when benchmarking a profile.
+    fn baseline_path(&self) -> Option<PathBuf> {
+        match (&self.source, self.args.bench) {
+            (Source::Profile(profile), Some(_)) => Some(profile.path.join("bench.json")),
+            _ => None,
+        }
+    }
+
+    /// Runs every variant of a day on the same input.
+    fn run_day(&self, day: usize, solutions: &[&Solution]) -> Result<DayRuns, String> {
+        // Read input and answers
+        let (input, expected): (Result<Arc<str>, Failure>, _) = match &self.source {
+            Source::Profile(profile) => {
+                // A missing input only fails the day, other errors stop the run.
+                let input = match profile.input(day) {
+                    Ok(input) => Ok(Arc::from(input)),
+                    Err(profiles::Error::MissingInput(path)) => Err(Failure::MissingInput(path)),
+                    Err(e) => return Err(e.to_string()),
+                };
+                let expected = profile.answers(day).map_err(|e| e.to_string())?;
+                (input, expected)
+            }
+            Source::Text(text) => (Ok(Arc::from(text.as_str())), [None, None]),
+        };
+
+        // Run solutions
+        let mut runs = Vec::new();
+        for solution in solutions {
+            let result = input.clone().and_then(|input| self.run_solution(solution, input));
+            runs.push(Run {
+                variant: solution.variant(),
+                result,
+            });
+        }
+        Ok(DayRuns {
+            day,
+            expected,
+            runs,
+        })
+    }
+
+    /// Runs a solution once, isolated from panics and subject to the time limit.
+    /// In benchmark mode, it is then measured repeatedly without a time limit.
+    fn run_solution(&self, solution: &Solution, input: Arc<str>) -> Result<Finished, Failure> {
+        let (run, run_phases) = (solution.run, solution.run_phases);
+        let run_once = move |input: &str| match run_phases {
+            Some(run_phases) => run_phases(input).map(|(answers, times)| (answers, Some(times))),
+            None => run(input).map(|answers| (answers, None)),
+        };
+
+        let first_input = input.clone();
+        let (first, allocs) = isolate::run(self.args.timeout, move || {
+            alloc::measure(|| bench::measure(None, || run_once(&first_input)))
+        })?;
+        let (first, mut stats) = first;
+        let ((res1, res2), mut phases) = first.map_err(Failure::InvalidInput)?;
+        if let Some(config) = self.args.bench {
+            (phases, stats) = isolate::run(None, move || {
+                let mut times = Vec::new();
+                let (_, stats) = bench::measure(Some(&config), || {
+                    // The first run already accepted the input.
+                    let (answers, phases) = run_once(&input).expect("input was parsed before");
+                    times.extend(phases);
+                    answers
+                });
+                // Leave out the warm-up runs.
+                let sampled = &times[times.len().saturating_sub(stats.samples)..];
+                let phases = (!sampled.is_empty()).then(|| PhaseTimes::mean(sampled));
+                (phases, stats)
+            })?;
+        }
+        Ok(Finished {
+            answers: [res1, res2],
+            stats,
+            phases,
+            allocs: self.args.alloc.then_some(allocs),
+        })
+    }
+
+    /// Prints the results as tables, or adds them to `records` for structured formats.
+    /// Returns the number of wrong answers.
+    fn print(self, records: &mut Vec<Record>) -> Result<usize, String> {
+        let title = match self.source {
+            Source::Profile(_) => format!("Profile: {}", self.name),
+            Source::Text(_) => format!("Input: {}", self.name),
+        };
+        match self.args.format {
+            Format::Table | Format::Markdown => {
+                let markdown = self.args.format == Format::Markdown;
+                let table = if self.args.bench.is_some() {
+                    self.bench_table()
+                } else {
+                    self.answer_table()
+                };
+                if markdown {
+                    println!("## {title}\n\n{table}\n");
+                } else {
+                    println!("{title}\n{table}\n");
+                }
+
+                // print long solutions after table
+                for day_runs in &self.days {
+                    let day = day_runs.day;
+                    let Some(reference) = day_runs.reference() else {
+                        continue;
+                    };
+                    for (part, res) in reference.answers.iter().enumerate() {
+                        if self.args.has_part(part + 1) && res.is_multiline() {
+                            if markdown {
+                                println!("Day {day:02}, part {}:\n```\n{res}```\n", part + 1);
+                            } else {
+                                println!("Day {day:02}, part {}:\n{res}\n", part + 1);
+                            }
+                        }
+                    }
+                }
+            }
+            Format::Json | Format::Csv => {
+                for DayRuns { day, runs, .. } in &self.days {
+                    for run in runs {
+                        let (answers, stats, phases, allocs) = match &run.result {
+                            Ok(Finished {
+                                answers: [a, b],
+                                stats,
+                                phases,
+                                allocs,
+                            }) => (
+                                [
+                                    Some(a.clone()).filter(|_| self.args.has_part(1)),
+                                    Some(b.clone()).filter(|_| self.args.has_part(2)),
+                                ],
+                                Some(*stats),
+                                *phases,
+                                *allocs,
+                            ),
+                            Err(_) => ([None, None], None, None, None),
+                        };
+                        records.push(Record {
+                            profile: self.name.clone(),
+                            day: *day,
+                            variant: run.variant,
+                            error: run.result.as_ref().err().cloned(),
+                            answers,
+                            stats,
+                            phases,
+                            allocs,
+                        });
+                    }
+                }
+            }
+        }
+
+        // print failures and disagreements between variants last, so they aren't missed
+        let mut failures = Vec::new();
+        for DayRuns { day, runs, .. } in &self.days {
+            for run in runs {
+                if let Err(failure) = &run.result {
+                    failures.push(format!("Day {day:02} ({}): {failure}", run.variant));
+                    if let Failure::InvalidInput(error) = failure {
+                        failures.extend(error.snippet().lines().map(|line| format!("  {line}")));
+                    }
+                }
+            }
+        }
+        if !failures.is_empty() {
+            self.note(&title);
+            self.note("Failures:");
+            for failure in failures {
+                self.note(&format!("  {failure}"));
+            }
+            self.note("");
+        }
+
+        let mismatches = self.mismatches();
+        if !mismatches.is_empty() {
+            self.note(&title);
+            self.note("Variants disagree:");
+            for mismatch in mismatches {
+                self.note(&format!("  {mismatch}"));
+            }
+            self.note("");
+        }
+
+        let wrong = self.wrong_answers();
+        if !wrong.is_empty() {
+            self.note(&title);
+            self.note("Wrong answers:");
+            for answer in &wrong {
+                self.note(&format!("  {answer}"));
+            }
+            self.note("");
+        }
+
+        if self.args.bench.is_some() {
+            self.report_regressions();
+            self.save_baseline()?;
+        }
+        Ok(wrong.len())
+    }
+
+    /// Whether answers are checked against known answers, and marked in the tables.
+    fn verifies(&self) -> bool {
+        matches!(self.source, Source::Profile(_))
+    }
+
+    /// Markers for the selected parts of a run, fx. `✓ ?`.
+    fn markers(&self, day_runs: &DayRuns, finished: &Finished) -> String {
+        let markers: Vec<_> = (1..=2)
+            .filter(|part| self.args.has_part(*part))
+            .map(|part| day_runs.verdict(part, &finished.answers[part - 1]).marker())
+            .collect();
+        markers.join(" ")
+    }
+
+    /// Compares the answers of each variant against the known answers.
+    fn wrong_answers(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        for day_runs in &self.days {
+            for (variant, finished) in day_runs.finished() {
+                for part in 1..=2 {
+                    let actual = &finished.answers[part - 1];
+                    if self.args.has_part(part) && day_runs.verdict(part, actual) == Verdict::Wrong
+                    {
+                        let expected = day_runs.expected[part - 1].as_ref().unwrap();
+                        res.push(format!(
+                            "Day {:02} ({variant}), part {part}: expected {}, got {}",
+                            day_runs.day,
+                            expected.to_string().trim_end(),
+                            actual.to_string().trim_end(),
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Lists the runs that got slower than the baseline by more than the threshold.
+    fn report_regressions(&self) {
+        let threshold = self.args.bench.unwrap().threshold;
+        let mut regressions = Vec::new();
+        for DayRuns { day, runs, .. } in &self.days {
+            for run in runs {
+                let (Some(old), Ok(new)) = (self.baseline.get(*day, run.variant), &run.result)
+                else {
+                    continue;
+                };
+                let Some(change) = baseline::change(old, &new.stats) else {
+                    continue;
+                };
+                if change > threshold {
+                    regressions.push(format!(
+                        "Day {day:02} ({}): {:.2?} -> {:.2?} ({:+.1}%)",
+                        run.variant,
+                        old.median,
+                        new.stats.median,
+                        change * 100.0
+                    ));
+                }
+            }
+        }
+        if !regressions.is_empty() {
+            self.note(&format!("Regressions above {:.1}%:", threshold * 100.0));
+            for regression in regressions {
+                self.note(&format!("  {regression}"));
+            }
+            self.note("");
+        }
+    }
+
+    /// Prints a line that isn't part of the results,
+    /// keeping stdout clean for structured formats.
+    fn note(&self, line: &str) {
+        if self.args.format.is_structured() {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+
+    fn new_table(&self) -> Table {
+        let mut table = Table::new();
+        if self.args.format == Format::Markdown {
+            table.load_preset(ASCII_MARKDOWN);
+        } else {
+            table.load_preset(UTF8_FULL_CONDENSED);
+        }
+        table
+    }
+
+    /// Adds an empty row before the totals, except in markdown where it would be visible.
+    fn add_spacer(&self, table: &mut Table) {
+        if self.args.format != Format::Markdown {
+            let columns = table.column_count();
+            table.add_row(vec![""; columns]);
+        }
+    }
+
+    /// Adds missing entries to the baseline, or overwrites it when requested.
+    fn save_baseline(mut self) -> Result<(), String> {
+        let Some(path) = self.baseline_path() else {
+            return Ok(());
+        };
+        let overwrite = self.args.bench.unwrap().save_baseline;
+        for DayRuns { day, runs, .. } in &self.days {
+            for run in runs {
+                let Ok(finished) = &run.result else {
+                    continue;
+                };
+                if overwrite || self.baseline.get(*day, run.variant).is_none() {
+                    self.baseline.insert(*day, run.variant, finished.stats);
+                }
+            }
+        }
+        self.baseline.save(&path)
+    }
+
+    /// Compares the answers of each variant against the first variant of the same day.
+    fn mismatches(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        for day_runs in &self.days {
+            let day = day_runs.day;
+            let mut finished = day_runs.finished();
+            let Some((reference_variant, reference)) = finished.next() else {
+                continue;
+            };
+            for (variant, other) in finished {
+                for part in 1..=2 {
+                    let (a, b) = (&reference.answers[part - 1], &other.answers[part - 1]);
+                    if self.args.has_part(part) && a != b {
+                        res.push(format!(
+                            "Day {day:02}, part {part}: {reference_variant} = {a}, {variant} = {b}"
+                        ));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// Table with the answers of each day, and one time column per variant.
+    /// The answers of the first variant are shown, the others are checked against them.
+    /// Answers are marked as correct, wrong or unknown when checking a profile.
+    fn answer_table(&self) -> Table {
+        let mut table = self.new_table();
+        let mut header = vec![String::from("Day")];
+        if self.args.has_part(1) {
+            header.push(String::from("Part 1"));
+        }
+        if self.args.has_part(2) {
+            header.push(String::from("Part 2"));
+        }
+        let parts = header.len() - 1;
+        let suffix = |v: &str| match self.variants.len() {
+            1 => String::new(),
+            _ => format!(" ({v})"),
+        };
+        header.extend(self.variants.iter().map(|v| format!("Time{}", suffix(v))));
+        if self.args.alloc {
+            for v in &self.variants {
+                let suffix = suffix(v);
+                header.extend(["Allocs", "Allocated", "Peak"].map(|c| format!("{c}{suffix}")));
+            }
+        }
+        table.set_header(header);
+
+        let mut totals = vec![Duration::ZERO; self.variants.len()];
+        for day_runs in &self.days {
+            let DayRuns { day, runs, .. } = day_runs;
+            let reference = day_runs.reference();
+            let mut row = vec![Cell::new(day.to_string()).set_alignment(CellAlignment::Right)];
+            for part in 1..=2 {
+                if !self.args.has_part(part) {
+                    continue;
+                }
+                let res = match reference {
+                    Some(reference) => {
+                        let expected = &reference.answers[part - 1];
+                        let mut res = if expected.is_multiline() {
+                            String::from("see below")
+                        } else {
+                            expected.to_string()
+                        };
+                        let disagrees = runs.iter().any(|run| {
+                            run.result.as_ref().is_ok_and(|r| r.answers[part - 1] != *expected)
+                        });
+                        if disagrees {
+                            res += " ≠";
+                        }
+                        if self.verifies() {
+                            // Any wrong variant makes the whole day wrong.
+                            let verdicts: Vec<_> = day_runs
+                                .finished()
+                                .map(|(_, r)| day_runs.verdict(part, &r.answers[part - 1]))
+                                .collect();
+                            let verdict = if verdicts.contains(&Verdict::Wrong) {
+                                Verdict::Wrong
+                            } else {
+                                verdicts[0]
+                            };
+                            res += " ";
+                            res += verdict.marker();
+                        }
+                        res
+                    }
+                    // Nothing finished, so show why the first one failed.
+                    None => match &runs[0].result {
+                        Err(failure) => failure.status().to_owned(),
+                        Ok(_) => unreachable!(),
+                    },
+                };
+                row.push(Cell::new(res).set_alignment(CellAlignment::Right));
+            }
+            for (variant, total) in self.variants.iter().zip(totals.iter_mut()) {
+                let time = runs.iter().find(|run| run.variant == *variant).map(|run| {
+                    match &run.result {
+                        Ok(Finished { stats, phases, .. }) => {
+                            *total += stats.mean;
+                            match phases {
+                                Some(phases) => {
+                                    format!("{:.2?} ({})", stats.mean, format_phases(phases))
+                                }
+                                None => format!("{:.2?}", stats.mean),
+                            }
+                        }
+                        Err(failure) => failure.status().to_owned(),
+                    }
+                });
+                row.push(Cell::new(time.unwrap_or_default()).set_alignment(CellAlignment::Right));
+            }
+            if self.args.alloc {
+                for variant in &self.variants {
+                    let allocs = runs.iter().find(|run| run.variant == *variant).and_then(|run| {
+                        run.result.as_ref().ok().and_then(|finished| finished.allocs)
+                    });
+                    row.extend(alloc_cells(allocs));
+                }
+            }
+            table.add_row(row);
+        }
+
+        self.add_spacer(&mut table);
+        let mut total = vec![Cell::new("Total")];
+        total.extend((0..parts).map(|_| Cell::new("")));
+        for time in &totals {
+            total.push(Cell::new(format!("{time:.2?}")).set_alignment(CellAlignment::Right));
+        }
+        table.add_row(total);
+        table
+    }
+
+    /// Table with timing statistics for each variant of each day.
+    /// Statistics are per solution, covering both parts,
+    /// with the mean time of each phase for phased solutions only.
+    fn bench_table(&self) -> Table {
+        let mut table = self.new_table();
+        let verify = self.verifies();
+        let mut header = vec!["Day", "Variant"];
+        if verify {
+            header.push("Answers");
+        }
+        header.extend(["Runs", "Min", "Median", "Mean", "Std. dev."]);
+        let phased = self.days.iter().any(|day_runs| {
+            day_runs.finished().any(|(_, finished)| finished.phases.is_some())
+        });
+        if phased {
+            header.push("Parse + Part 1 + Part 2");
+        }
+        if self.args.alloc {
+            header.extend(["Allocs", "Allocated", "Peak"]);
+        }
+        let compare = !self.baseline.is_empty();
+        if compare {
+            header.push("Δ Median");
+        }
+        table.set_header(header);
+        let right = |s: String| Cell::new(s).set_alignment(CellAlignment::Right);
+        let threshold = self.args.bench.unwrap().threshold;
+
+        let mut totals = vec![[Duration::ZERO; 3]; self.variants.len()];
+        for day_runs in &self.days {
+            let DayRuns { day, runs, .. } = day_runs;
+            for run in runs {
+                let finished = match &run.result {
+                    Ok(finished) => finished,
+                    Err(failure) => {
+                        table.add_row(vec![
+                            right(day.to_string()),
+                            Cell::new(run.variant),
+                            Cell::new(failure.status()),
+                        ]);
+                        continue;
+                    }
+                };
+                let stats = finished.stats;
+                let Stats { samples, min, median, mean, stddev } = stats;
+                let idx = self.variants.iter().position(|v| *v == run.variant).unwrap();
+                for (total, time) in totals[idx].iter_mut().zip([min, median, mean]) {
+                    *total += time;
+                }
+                let mut row = vec![right(day.to_string()), Cell::new(run.variant)];
+                if verify {
+                    row.push(Cell::new(self.markers(day_runs, finished)));
+                }
+                row.extend([
+                    right(samples.to_string()),
+                    right(format!("{min:.2?}")),
+                    right(format!("{median:.2?}")),
+                    right(format!("{mean:.2?}")),
+                    right(format!("±{stddev:.2?}")),
+                ]);
+                if phased {
+                    row.push(right(finished.phases.as_ref().map(format_phases).unwrap_or_default()));
+                }
+                if self.args.alloc {
+                    row.extend(alloc_cells(finished.allocs));
+                }
+                if compare {
+                    let old = self.baseline.get(*day, run.variant);
+                    let delta = match old.map(|old| baseline::change(old, &stats)) {
+                        Some(Some(change)) => {
+                            let cell = right(format!("{:+.1}%", change * 100.0));
+                            if change > threshold {
+                                cell.fg(Color::Red)
+                            } else if change < -threshold {
+                                cell.fg(Color::Green)
+                            } else {
+                                cell
+                            }
+                        }
+                        Some(None) => right(String::from("n/a")),
+                        None => right(String::from("new")),
+                    };
+                    row.push(delta);
+                }
+                table.add_row(row);
+            }
+        }
+
+        self.add_spacer(&mut table);
+        for (variant, [min, median, mean]) in self.variants.iter().zip(totals) {
+            let mut row = vec![Cell::new("Total"), Cell::new(variant)];
+            if verify {
+                row.push(Cell::new(""));
+            }
+            row.extend([
+                Cell::new(""),
+                right(format!("{min:.2?}")),
+                right(format!("{median:.2?}")),
+                right(format!("{mean:.2?}")),
+                Cell::new(""),
+            ]);
+            table.add_row(row);
+        }
+        table
+    }
+}
+
+/// Formats the time of each phase, fx. `1.20µs + 3.00ms + 4.50ms`.
+fn format_phases(phases: &PhaseTimes) -> String {
+    format!("{:.2?} + {:.2?} + {:.2?}", phases.parse, phases.part1, phases.part2)
+}
+
+/// Allocation count, bytes allocated and peak memory, or empty cells.
+fn alloc_cells(allocs: Option<AllocStats>) -> [Cell; 3] {
+    let cells = match allocs {
+        Some(a) => [
+            a.allocations.to_string(),
+            alloc::format_bytes(a.bytes),
+            alloc::format_bytes(a.peak),
+        ],
+        None => Default::default(),
+    };
+    cells.map(|c| Cell::new(c).set_alignment(CellAlignment::Right))
+}
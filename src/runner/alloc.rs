@@ -0,0 +1,155 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+/// A global allocator that counts the allocations of each thread, once `enable` has been called.
+/// Install it in the binary with `#[global_allocator]`.
+pub struct Counting;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    static COUNTS: Cell<Counts> = const { Cell::new(Counts::ZERO) };
+}
+
+#[derive(Clone, Copy)]
+struct Counts {
+    allocations: u64,
+    bytes: u64,
+    /// Can go negative when freeing memory that was allocated before measuring.
+    live: i64,
+    peak: i64,
+}
+
+impl Counts {
+    const ZERO: Self = Self {
+        allocations: 0,
+        bytes: 0,
+        live: 0,
+        peak: 0,
+    };
+}
+
+/// Allocations made while running a solution.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    /// Total size of all allocations, including the ones that were freed again.
+    pub bytes: u64,
+    /// Largest amount of memory allocated at the same time.
+    pub peak: u64,
+}
+
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Runs `f` and counts the allocations it makes on the current thread.
+/// Only meaningful when `Counting` is the global allocator and counting is enabled.
+pub fn measure<R>(f: impl FnOnce() -> R) -> (R, AllocStats) {
+    COUNTS.set(Counts::ZERO);
+    let res = f();
+    let counts = COUNTS.get();
+    let stats = AllocStats {
+        allocations: counts.allocations,
+        bytes: counts.bytes,
+        peak: counts.peak.max(0) as u64,
+    };
+    (res, stats)
+}
+
+/// Records an allocation of `added` bytes and a deallocation of `removed` bytes.
+fn record(added: usize, removed: usize) {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    // Thread-locals are unavailable while a thread shuts down, so those allocations aren't counted.
+    let _ = COUNTS.try_with(|counts| {
+        let mut c = counts.get();
+        if added > 0 {
+            c.allocations += 1;
+            c.bytes += added as u64;
+        }
+        c.live += added as i64 - removed as i64;
+        c.peak = c.peak.max(c.live);
+        counts.set(c);
+    });
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        record(0, layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            record(new_size, layout.size());
+        }
+        new
+    }
+}
+
+/// Formats a number of bytes with a binary unit, fx. `1.50 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{size:.2} {}", UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes() {
+        assert_eq!(format_bytes(1000), "1000 B");
+        assert_eq!(format_bytes(1536), "1.50 KiB");
+        assert_eq!(format_bytes(3 << 30), "3.00 GiB");
+    }
+
+    #[test]
+    fn counting() {
+        // The test binary doesn't use `Counting`, so feed it allocations directly.
+        enable();
+        let (_, stats) = measure(|| {
+            record(100, 0);
+            record(50, 0);
+            record(0, 100);
+            record(200, 50);
+        });
+        let expected = AllocStats {
+            allocations: 3,
+            bytes: 350,
+            peak: 200,
+        };
+        assert_eq!(stats, expected);
+    }
+}
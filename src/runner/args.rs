@@ -0,0 +1,283 @@
+use std::{ops::RangeInclusive, path::PathBuf, time::Duration};
+
+use crate::runner::{bench::BenchConfig, output::Format};
+
+pub const USAGE: &str = "\
+Usage: aoc2023 [OPTIONS]
+       aoc2023 new-day <DAY>
+
+Commands:
+  new-day <DAY>           Create the solution of a day from a template, and register it
+
+Options:
+  -d, --day <DAYS>        Only run the given days, fx. `5`, `5..10`, `5..=10`, `..8` or `1,3,7`
+  -p, --profile <NAME>    Only run the given profile (can be repeated)
+      --part <PART>       Only show the answers for part 1 or 2
+  -i, --input <PATH>      Read the input from a file instead of `data/`, `-` reads from stdin
+  -b, --bench             Run each solution repeatedly and report timing statistics
+  -t, --timeout <SECS>    Give up on solutions that take longer than this
+  -a, --alloc             Count allocations and peak memory use of each solution
+  -j, --jobs <N>          Run up to N days at once, ignored in benchmark mode [default: 1]
+  -w, --watch             Rebuild and rerun days when their source or inputs change
+      --bench-time <SECS> Time spent measuring each solution [default: 1]
+      --threshold <PCT>   Slowdown from the baseline in `data/<PROFILE>/bench.json`
+                          that is reported as a regression [default: 10]
+      --save-baseline     Replace the baseline with the results of this benchmark run,
+                          instead of only adding the missing days
+                          (these three options require `--bench`)
+  -f, --format <FORMAT>   Output format: `table`, `markdown`, `json` or `csv` [default: table]
+  -h, --help              Print this message";
+
+/// Command-line arguments of the runner.
+#[derive(Debug, Default)]
+pub struct Args {
+    /// Day filter, empty means all days.
+    pub days: Vec<RangeInclusive<usize>>,
+    /// Profile filter, empty means all profiles.
+    pub profiles: Vec<String>,
+    /// Part filter, `None` means both parts.
+    pub part: Option<usize>,
+    /// Single input to run instead of the inputs in `data/`.
+    pub input: Option<Input>,
+    /// Benchmark settings, `None` means every solution runs once.
+    pub bench: Option<BenchConfig>,
+    /// Time limit for a single run of a solution.
+    pub timeout: Option<Duration>,
+    /// Count allocations, which requires `alloc::Counting` to be the global allocator.
+    pub alloc: bool,
+    /// Number of days to run at once, 0 and 1 both mean one at a time.
+    pub jobs: usize,
+    /// Keep running, rerunning days when their files change.
+    pub watch: bool,
+    /// Scaffold this day instead of running anything.
+    pub new_day: Option<usize>,
+    pub format: Format,
+    pub help: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Input {
+    File(PathBuf),
+    Stdin,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut res = Self::default();
+        // Options that only apply to benchmarks, which are rejected without `--bench`.
+        let mut bench_option = None;
+        let mut bench = false;
+        while let Some(arg) = args.next() {
+            // Support both `--day 5` and `--day=5`.
+            let (flag, mut value) = match arg.split_once('=') {
+                Some((flag, value)) if flag.starts_with("--") => {
+                    (flag.to_owned(), Some(value.to_owned()))
+                }
+                _ => (arg, None),
+            };
+            let mut value = |name: &str| {
+                value
+                    .take()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("missing value for `{name}`"))
+            };
+            match flag.as_str() {
+                "-d" | "--day" => {
+                    let days = value(&flag)?;
+                    for days in days.split(',') {
+                        res.days.push(parse_days(days)?);
+                    }
+                }
+                "-p" | "--profile" => res.profiles.push(value(&flag)?),
+                "--part" => match value(&flag)?.as_str() {
+                    "1" => res.part = Some(1),
+                    "2" => res.part = Some(2),
+                    part => return Err(format!("invalid part `{part}`, expected 1 or 2")),
+                },
+                "-i" | "--input" => {
+                    res.input = Some(match value(&flag)?.as_str() {
+                        "-" => Input::Stdin,
+                        path => Input::File(PathBuf::from(path)),
+                    })
+                }
+                "-b" | "--bench" => {
+                    res.bench.get_or_insert_with(BenchConfig::default);
+                    bench = true;
+                }
+                "-t" | "--timeout" => res.timeout = Some(parse_secs(&value(&flag)?)?),
+                "-a" | "--alloc" => res.alloc = true,
+                "-j" | "--jobs" => {
+                    let jobs = value(&flag)?;
+                    res.jobs = jobs
+                        .parse()
+                        .ok()
+                        .filter(|jobs| *jobs > 0)
+                        .ok_or_else(|| format!("invalid number of jobs `{jobs}`"))?;
+                }
+                "-w" | "--watch" => res.watch = true,
+                "--bench-time" => {
+                    bench_option.get_or_insert_with(|| flag.clone());
+                    let time = parse_secs(&value(&flag)?)?;
+                    let bench = res.bench.get_or_insert_with(BenchConfig::default);
+                    bench.time = time;
+                    bench.warm_up = time / 10;
+                }
+                "--threshold" => {
+                    bench_option.get_or_insert_with(|| flag.clone());
+                    let percent = value(&flag)?;
+                    let threshold = percent
+                        .trim_end_matches('%')
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|p| *p >= 0.0)
+                        .ok_or_else(|| format!("invalid percentage `{percent}`"))?;
+                    res.bench.get_or_insert_with(BenchConfig::default).threshold = threshold / 100.0;
+                }
+                "--save-baseline" => {
+                    bench_option.get_or_insert_with(|| flag.clone());
+                    res.bench.get_or_insert_with(BenchConfig::default).save_baseline = true;
+                }
+                "-f" | "--format" => {
+                    let format = value(&flag)?;
+                    res.format = Format::parse(&format)
+                        .ok_or_else(|| format!("invalid format `{format}`"))?;
+                }
+                "-h" | "--help" => res.help = true,
+                "new-day" => {
+                    let day = value(&flag)?;
+                    match parse_days(&day)? {
+                        days if days.start() == days.end() => res.new_day = Some(*days.start()),
+                        _ => return Err(format!("`new-day` takes a single day, got `{day}`")),
+                    }
+                }
+                _ => return Err(format!("unexpected argument `{flag}`")),
+            }
+        }
+        if let (Some(option), false) = (bench_option, bench) {
+            return Err(format!("`{option}` requires `--bench`"));
+        }
+        if res.input.is_some() && !res.profiles.is_empty() {
+            return Err("`--input` cannot be combined with `--profile`".to_owned());
+        }
+        if res.watch && (res.input.is_some() || res.bench.is_some()) {
+            return Err("`--watch` cannot be combined with `--input` or `--bench`".to_owned());
+        }
+        Ok(res)
+    }
+
+    pub fn has_day(&self, day: usize) -> bool {
+        self.days.is_empty() || self.days.iter().any(|days| days.contains(&day))
+    }
+
+    pub fn has_profile(&self, profile: &str) -> bool {
+        self.profiles.is_empty() || self.profiles.iter().any(|p| p == profile)
+    }
+
+    pub fn has_part(&self, part: usize) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+}
+
+fn parse_secs(secs: &str) -> Result<Duration, String> {
+    secs.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("invalid duration `{secs}`"))
+}
+
+/// Parses a single day or a non-empty range of days, using Rust range syntax.
+fn parse_days(days: &str) -> Result<RangeInclusive<usize>, String> {
+    if days.is_empty() {
+        return Err("missing day".to_owned());
+    }
+    let parse = |day: &str, default: usize| -> Result<usize, String> {
+        if day.is_empty() {
+            return Ok(default);
+        }
+        match day.parse::<usize>() {
+            Ok(day @ 1..=25) => Ok(day),
+            _ => Err(format!(
+                "invalid day `{day}`, expected a number from 1 to 25"
+            )),
+        }
+    };
+    let range = if let Some((a, b)) = days.split_once("..=") {
+        parse(a, 1)?..=parse(b, 25)?
+    } else if let Some((a, b)) = days.split_once("..") {
+        let end = if b.is_empty() { 25 } else { parse(b, 25)? - 1 };
+        parse(a, 1)?..=end
+    } else {
+        let day = parse(days, 0)?;
+        day..=day
+    };
+    if range.is_empty() {
+        return Err(format!("empty range of days `{days}`"));
+    }
+    Ok(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &str) -> Result<Args, String> {
+        Args::parse(args.split_whitespace().map(str::to_owned))
+    }
+
+    #[test]
+    fn days() {
+        let args = parse("--day 5..10 -d 12 --day=20..=21,24").unwrap();
+        assert_eq!(args.days, vec![5..=9, 12..=12, 20..=21, 24..=24]);
+        assert!(args.has_day(9) && !args.has_day(10) && args.has_day(24));
+        assert_eq!(parse("--day ..3").unwrap().days, vec![1..=2]);
+        assert_eq!(parse("--day 23..").unwrap().days, vec![23..=25]);
+        assert!(parse("--day 26").is_err());
+        assert!(parse("--day").is_err());
+        assert_eq!(parse("--day=").unwrap_err(), "missing day");
+        assert_eq!(parse("--day 3,,5").unwrap_err(), "missing day");
+        assert_eq!(parse("--day 5..3").unwrap_err(), "empty range of days `5..3`");
+        assert_eq!(parse("--day 5..=4").unwrap_err(), "empty range of days `5..=4`");
+        assert_eq!(parse("--day 5..5").unwrap_err(), "empty range of days `5..5`");
+        assert_eq!(parse("--day ..1").unwrap_err(), "empty range of days `..1`");
+    }
+
+    #[test]
+    fn filters() {
+        let err = parse("--profile alice -p bob --input -").unwrap_err();
+        assert!(err.contains("--input"));
+        let args = parse("--profile alice -p bob --part 2").unwrap();
+        assert!(args.has_profile("bob") && !args.has_profile("carol"));
+        assert!(args.has_part(2) && !args.has_part(1));
+        assert_eq!(parse("-i -").unwrap().input, Some(Input::Stdin));
+        assert!(parse("--part 3").is_err());
+        assert!(parse("--frobnicate").is_err());
+        assert_eq!(parse("--format=json").unwrap().format, Format::Json);
+        assert!(parse("--format yaml").is_err());
+        assert_eq!(parse("-j 4").unwrap().jobs, 4);
+        assert!(parse("--jobs 0").is_err());
+        assert!(parse("--watch -d 5").unwrap().watch);
+        assert!(parse("-w --bench").is_err());
+        assert_eq!(parse("new-day 19").unwrap().new_day, Some(19));
+        assert!(parse("new-day 19..21").is_err());
+    }
+
+    #[test]
+    fn bench() {
+        assert_eq!(parse("").unwrap().bench, None);
+        assert_eq!(parse("--bench").unwrap().bench, Some(BenchConfig::default()));
+        let bench = parse("--bench-time 0.5 -b").unwrap().bench.unwrap();
+        assert_eq!(bench.time, Duration::from_millis(500));
+        assert_eq!(bench.warm_up, Duration::from_millis(50));
+        assert!(parse("--bench-time -1").is_err());
+        let timeout = parse("--timeout 2.5").unwrap().timeout;
+        assert_eq!(timeout, Some(Duration::from_millis(2500)));
+        let bench = parse("-b --threshold 25% --save-baseline").unwrap();
+        let bench = bench.bench.unwrap();
+        assert_eq!(bench.threshold, 0.25);
+        assert!(bench.save_baseline);
+        for option in ["--bench-time 2", "--threshold 5", "--save-baseline"] {
+            let err = parse(option).unwrap_err();
+            assert!(err.contains("requires `--bench`"), "{err}");
+        }
+    }
+}
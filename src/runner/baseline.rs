@@ -0,0 +1,134 @@
+use std::{collections::BTreeMap, path::Path, time::Duration};
+
+use serde_json::{json, Map, Value};
+
+use crate::runner::bench::Stats;
+
+/// Benchmark results from a previous run, keyed by day and variant.
+///
+/// Stored as JSON, with all durations in nanoseconds:
+/// `{ "day01": { "solutions": { "samples": 100, "min_ns": 1200, ... } } }`
+#[derive(Debug, Default, PartialEq)]
+pub struct Baseline {
+    entries: BTreeMap<(usize, String), Stats>,
+}
+
+impl Baseline {
+    /// Loads a baseline from `path`, or returns an empty one if the file doesn't exist.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("couldn't read {}: {e}", path.display())),
+        };
+        let json = serde_json::from_str(&text)
+            .map_err(|e| format!("couldn't parse {}: {e}", path.display()))?;
+        Self::from_json(&json).ok_or_else(|| format!("malformed baseline: {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = serde_json::to_string_pretty(&self.to_json()).unwrap();
+        std::fs::write(path, text + "\n")
+            .map_err(|e| format!("couldn't write {}: {e}", path.display()))
+    }
+
+    pub fn get(&self, day: usize, variant: &str) -> Option<&Stats> {
+        self.entries.get(&(day, variant.to_owned()))
+    }
+
+    pub fn insert(&mut self, day: usize, variant: &str, stats: Stats) {
+        self.entries.insert((day, variant.to_owned()), stats);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    fn to_json(&self) -> Value {
+        let mut days = Map::new();
+        for ((day, variant), stats) in &self.entries {
+            let entry = days
+                .entry(format!("day{day:02}"))
+                .or_insert_with(|| Value::Object(Map::new()));
+            entry.as_object_mut().unwrap().insert(
+                variant.clone(),
+                json!({
+                    "samples": stats.samples,
+                    "min_ns": stats.min.as_nanos() as u64,
+                    "median_ns": stats.median.as_nanos() as u64,
+                    "mean_ns": stats.mean.as_nanos() as u64,
+                    "stddev_ns": stats.stddev.as_nanos() as u64,
+                }),
+            );
+        }
+        Value::Object(days)
+    }
+
+    fn from_json(json: &Value) -> Option<Self> {
+        let mut res = Self::default();
+        for (day, variants) in json.as_object()? {
+            let day = day.strip_prefix("day")?.parse::<usize>().ok()?;
+            for (variant, stats) in variants.as_object()? {
+                let nanos = |key: &str| Some(Duration::from_nanos(stats.get(key)?.as_u64()?));
+                let stats = Stats {
+                    samples: stats.get("samples")?.as_u64()? as usize,
+                    min: nanos("min_ns")?,
+                    median: nanos("median_ns")?,
+                    mean: nanos("mean_ns")?,
+                    stddev: nanos("stddev_ns")?,
+                };
+                res.insert(day, variant, stats);
+            }
+        }
+        Some(res)
+    }
+}
+
+/// Relative change in median run time, fx. `0.25` when `new` is 25% slower than `old`.
+/// Returns `None` if the old median is zero, fx. in a hand-edited baseline,
+/// as there is nothing to compare against.
+pub fn change(old: &Stats, new: &Stats) -> Option<f64> {
+    if old.median.is_zero() {
+        return None;
+    }
+    Some(new.median.as_secs_f64() / old.median.as_secs_f64() - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(median: u64) -> Stats {
+        let us = Duration::from_micros;
+        Stats {
+            samples: 10,
+            min: us(median - 1),
+            median: us(median),
+            mean: us(median + 1),
+            stddev: us(2),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut baseline = Baseline::default();
+        baseline.insert(1, "solutions", stats(10));
+        baseline.insert(1, "optimized", stats(5));
+        baseline.insert(12, "solutions", stats(300));
+        let json = baseline.to_json();
+        assert_eq!(json["day12"]["solutions"]["median_ns"], 300_000);
+        assert_eq!(Baseline::from_json(&json), Some(baseline));
+        assert_eq!(Baseline::from_json(&json!({ "dayX": {} })), None);
+    }
+
+    #[test]
+    fn relative_change() {
+        assert_eq!(change(&stats(100), &stats(125)), Some(0.25));
+        assert_eq!(change(&stats(100), &stats(50)), Some(-0.5));
+        let zero = Stats {
+            median: Duration::ZERO,
+            ..stats(1)
+        };
+        assert_eq!(change(&zero, &stats(50)), None);
+    }
+}
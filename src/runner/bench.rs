@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+/// Settings for benchmark mode.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BenchConfig {
+    /// How long to spend measuring each solution, after warming up.
+    pub time: Duration,
+    /// How long to run each solution before measuring it.
+    pub warm_up: Duration,
+    /// Lower bound on the number of measurements, even if it exceeds `time`.
+    pub min_samples: usize,
+    /// Relative slowdown from the baseline that counts as a regression, fx. `0.1` for 10%.
+    pub threshold: f64,
+    /// Overwrite existing entries in the baseline instead of only adding missing ones.
+    pub save_baseline: bool,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            time: Duration::from_secs(1),
+            warm_up: Duration::from_millis(100),
+            min_samples: 5,
+            threshold: 0.1,
+            save_baseline: false,
+        }
+    }
+}
+
+/// Summary statistics of a set of timing measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn new(samples: &mut [Duration]) -> Self {
+        assert!(!samples.is_empty(), "no samples");
+        samples.sort_unstable();
+        let n = samples.len();
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_nanos() as f64 - mean.as_nanos() as f64).powi(2))
+            .sum::<f64>()
+            / n as f64;
+        Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean,
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+/// Times `f`, either a single time or repeatedly according to `config`.
+/// Returns the result of the last call.
+pub fn measure<R>(config: Option<&BenchConfig>, mut f: impl FnMut() -> R) -> (R, Stats) {
+    let mut time = || {
+        let start = Instant::now();
+        let res = f();
+        let end = Instant::now();
+        (res, end.duration_since(start))
+    };
+
+    let Some(config) = config else {
+        let (res, delta) = time();
+        return (res, Stats::new(&mut [delta]));
+    };
+
+    // Warm up, running at least once.
+    let mut elapsed = Duration::ZERO;
+    loop {
+        let (_, delta) = time();
+        elapsed += delta;
+        if elapsed >= config.warm_up {
+            break;
+        }
+    }
+
+    // Measure
+    let mut samples = Vec::new();
+    let mut elapsed = Duration::ZERO;
+    loop {
+        let (res, delta) = time();
+        samples.push(delta);
+        elapsed += delta;
+        if elapsed >= config.time && samples.len() >= config.min_samples {
+            return (res, Stats::new(&mut samples));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(&mut [ms(4), ms(2), ms(4), ms(4), ms(5), ms(5), ms(7), ms(9)]);
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.min, ms(2));
+        assert_eq!(stats.median, ms(9) / 2);
+        assert_eq!(stats.mean, ms(5));
+        assert_eq!(stats.stddev, ms(2));
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt::Display,
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    path::PathBuf,
+    sync::{mpsc, Once},
+    time::Duration,
+};
+
+use crate::util::parsers::ParseError;
+
+/// Why a solution didn't produce any answers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The input file for the day couldn't be read.
+    MissingInput(PathBuf),
+    /// The solution rejected the input.
+    InvalidInput(ParseError),
+    /// The solution panicked, with the panic message and location.
+    Panic(String),
+    /// The solution didn't finish within the time limit.
+    Timeout(Duration),
+}
+
+impl Failure {
+    /// Short description for table cells.
+    pub fn status(&self) -> &'static str {
+        match self {
+            Failure::MissingInput(_) => "missing input",
+            Failure::InvalidInput(_) => "invalid input",
+            Failure::Panic(_) => "panicked",
+            Failure::Timeout(_) => "timed out",
+        }
+    }
+}
+
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::MissingInput(path) => write!(f, "missing input: {}", path.display()),
+            Failure::InvalidInput(error) => write!(f, "invalid input: {error}"),
+            Failure::Panic(message) => write!(f, "panicked: {message}"),
+            Failure::Timeout(limit) => write!(f, "timed out after {limit:.2?}"),
+        }
+    }
+}
+
+thread_local! {
+    /// Whether panics on this thread are caught by `run`.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+    /// Message of the last panic caught on this thread.
+    static MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning panics into a `Failure` instead of unwinding further.
+///
+/// With a timeout, `f` runs on a separate thread.
+/// Threads can't be killed, so when the time limit is exceeded,
+/// the thread is left running in the background until the process exits.
+pub fn run<R, F>(timeout: Option<Duration>, f: F) -> Result<R, Failure>
+where
+    R: Send + 'static,
+    F: FnOnce() -> R + Send + 'static,
+{
+    install_hook();
+    let Some(timeout) = timeout else {
+        return catch(f);
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    std::thread::Builder::new()
+        .name(String::from("solution"))
+        // Match the main thread, since some solutions recurse deeply.
+        .stack_size(8 << 20)
+        .spawn(move || {
+            // The receiver is gone if we timed out, so there's nobody to tell.
+            let _ = sender.send(catch(f));
+        })
+        .expect("failed to spawn thread");
+    match receiver.recv_timeout(timeout) {
+        Ok(res) => res,
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(Failure::Timeout(timeout)),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(Failure::Panic(String::from("thread died")))
+        }
+    }
+}
+
+fn catch<R>(f: impl FnOnce() -> R) -> Result<R, Failure> {
+    CAPTURING.set(true);
+    let res = panic::catch_unwind(AssertUnwindSafe(f));
+    CAPTURING.set(false);
+    res.map_err(|payload| {
+        let message = MESSAGE.take().unwrap_or_else(|| payload_message(&*payload));
+        Failure::Panic(message)
+    })
+}
+
+/// Replaces the panic hook with one that stays quiet about panics caught by `run`,
+/// so they don't fill the output with backtraces.
+/// Panics anywhere else are passed on to the default hook.
+fn install_hook() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info: &PanicHookInfo| {
+            if CAPTURING.get() {
+                let mut message = payload_message(info.payload());
+                if let Some(location) = info.location() {
+                    message += &format!(" at {location}");
+                }
+                MESSAGE.set(Some(message));
+            } else {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn payload_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics() {
+        assert_eq!(run(None, || 5), Ok(5));
+        let Err(Failure::Panic(message)) = run(None, || -> u32 { todo!() }) else {
+            panic!("expected a panic");
+        };
+        assert!(message.starts_with("not yet implemented at src/runner/isolate.rs"));
+        let Err(Failure::Panic(message)) = run(Some(Duration::from_secs(5)), || {
+            "x".parse::<u32>().expect("custom message")
+        }) else {
+            panic!("expected a panic");
+        };
+        assert!(message.starts_with("custom message: "));
+    }
+
+    #[test]
+    fn timeout() {
+        let limit = Duration::from_millis(10);
+        let res = run(Some(limit), || std::thread::sleep(Duration::from_secs(5)));
+        assert_eq!(res, Err(Failure::Timeout(limit)));
+        assert_eq!(run(Some(Duration::from_secs(5)), || 5), Ok(5));
+    }
+}
@@ -0,0 +1,244 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::{
+    registry::PhaseTimes,
+    runner::{alloc::AllocStats, bench::Stats, isolate::Failure},
+    util::answer::Answer,
+};
+
+/// How the results of a run are printed.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable tables.
+    #[default]
+    Table,
+    /// Tables in markdown, fx. for pasting into the README.
+    Markdown,
+    /// A single JSON array containing a `Record` object for each run.
+    Json,
+    /// A CSV file containing a `Record` row for each run.
+    Csv,
+}
+
+impl Format {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "table" => Some(Self::Table),
+            "markdown" | "md" => Some(Self::Markdown),
+            "json" => Some(Self::Json),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+
+    /// Whether this format is meant for other programs.
+    /// Everything besides the results goes to stderr in that case.
+    pub fn is_structured(self) -> bool {
+        matches!(self, Self::Json | Self::Csv)
+    }
+}
+
+/// The result of running a single variant of a day, for structured formats.
+pub struct Record {
+    pub profile: String,
+    pub day: usize,
+    pub variant: &'static str,
+    /// Why the solution didn't finish, if it didn't.
+    pub error: Option<Failure>,
+    /// Answers, `None` when the part was filtered out or the solution failed.
+    pub answers: [Option<Answer>; 2],
+    /// Timings, `None` when the solution failed.
+    pub stats: Option<Stats>,
+    /// Average time of each phase, for solutions that are split into phases.
+    pub phases: Option<PhaseTimes>,
+    /// Allocations, when counting them.
+    pub allocs: Option<AllocStats>,
+}
+
+impl Record {
+    fn status(&self) -> &'static str {
+        self.error.as_ref().map_or("ok", Failure::status)
+    }
+
+    /// Timing statistics in nanoseconds, in the same order as in `FIELDS`.
+    fn timings(&self) -> [Option<u64>; 7] {
+        let nanos = |t: Duration| t.as_nanos() as u64;
+        let [min, median, mean, stddev] = match self.stats {
+            Some(s) => [s.min, s.median, s.mean, s.stddev].map(|t| Some(nanos(t))),
+            None => [None; 4],
+        };
+        let [parse, part1, part2] = match self.phases {
+            Some(p) => [p.parse, p.part1, p.part2].map(|t| Some(nanos(t))),
+            None => [None; 3],
+        };
+        [min, median, mean, stddev, parse, part1, part2]
+    }
+
+    /// Allocation counts, in the same order as in `FIELDS`.
+    fn allocations(&self) -> [Option<u64>; 3] {
+        match self.allocs {
+            Some(a) => [a.allocations, a.bytes, a.peak].map(Some),
+            None => [None; 3],
+        }
+    }
+}
+
+const FIELDS: [&str; 18] = [
+    "profile",
+    "day",
+    "variant",
+    "status",
+    "error",
+    "part1",
+    "part2",
+    "samples",
+    "min_ns",
+    "median_ns",
+    "mean_ns",
+    "stddev_ns",
+    "parse_ns",
+    "part1_ns",
+    "part2_ns",
+    "allocations",
+    "allocated_bytes",
+    "peak_bytes",
+];
+
+pub fn json(records: &[Record]) -> String {
+    let records: Vec<Value> = records
+        .iter()
+        .map(|r| {
+            let [min, median, mean, stddev, parse, part1, part2] = r.timings();
+            let [allocations, allocated, peak] = r.allocations();
+            json!({
+                "profile": r.profile,
+                "day": r.day,
+                "variant": r.variant,
+                "status": r.status(),
+                "error": r.error.as_ref().map(Failure::to_string),
+                "part1": r.answers[0].as_ref().map(Answer::to_json),
+                "part2": r.answers[1].as_ref().map(Answer::to_json),
+                "samples": r.stats.map(|s| s.samples),
+                "min_ns": min,
+                "median_ns": median,
+                "mean_ns": mean,
+                "stddev_ns": stddev,
+                "parse_ns": parse,
+                "part1_ns": part1,
+                "part2_ns": part2,
+                "allocations": allocations,
+                "allocated_bytes": allocated,
+                "peak_bytes": peak,
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&records).unwrap()
+}
+
+pub fn csv(records: &[Record]) -> String {
+    let mut res = FIELDS.join(",") + "\n";
+    let optional = |x: Option<u64>| x.map(|x| x.to_string()).unwrap_or_default();
+    for r in records {
+        let mut fields = vec![
+            csv_escape(&r.profile),
+            r.day.to_string(),
+            csv_escape(r.variant),
+            r.status().to_owned(),
+            csv_escape(&r.error.as_ref().map(Failure::to_string).unwrap_or_default()),
+            csv_escape(&r.answers[0].as_ref().map(Answer::to_string).unwrap_or_default()),
+            csv_escape(&r.answers[1].as_ref().map(Answer::to_string).unwrap_or_default()),
+            optional(r.stats.map(|s| s.samples as u64)),
+        ];
+        fields.extend(r.timings().map(optional));
+        fields.extend(r.allocations().map(optional));
+        res += &fields.join(",");
+        res.push('\n');
+    }
+    res
+}
+
+/// Quotes a CSV field if it contains separators, quotes or newlines.
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records() -> Vec<Record> {
+        let stats = Some(Stats::new(&mut [Duration::from_micros(3)]));
+        vec![
+            Record {
+                profile: String::from("alice"),
+                day: 1,
+                variant: "solutions",
+                error: None,
+                answers: [Some(Answer::Int(142)), None],
+                stats,
+                phases: Some(PhaseTimes {
+                    parse: Duration::from_micros(1),
+                    part1: Duration::from_micros(1),
+                    part2: Duration::ZERO,
+                }),
+                allocs: Some(AllocStats {
+                    allocations: 2,
+                    bytes: 64,
+                    peak: 48,
+                }),
+            },
+            Record {
+                profile: String::from("bob"),
+                day: 10,
+                variant: "optimized",
+                error: None,
+                answers: [Some(Answer::from("#.\n.#")), Some(Answer::from("a,\"b\""))],
+                stats,
+                phases: None,
+                allocs: None,
+            },
+            Record {
+                profile: String::from("bob"),
+                day: 11,
+                variant: "solutions",
+                error: Some(Failure::Timeout(Duration::from_secs(2))),
+                answers: [None, None],
+                stats: None,
+                phases: None,
+                allocs: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn to_json() {
+        let json: Value = serde_json::from_str(&json(&records())).unwrap();
+        assert_eq!(json[0]["part1"], 142);
+        assert_eq!(json[0]["part2"], Value::Null);
+        assert_eq!(json[1]["part1"], json!(["#.", ".#"]));
+        assert_eq!(json[1]["mean_ns"], 3000);
+        assert_eq!(json[0]["part1_ns"], 1000);
+        assert_eq!(json[1]["part1_ns"], Value::Null);
+        assert_eq!(json[0]["peak_bytes"], 48);
+        assert_eq!(json[2]["status"], "timed out");
+        assert_eq!(json[2]["error"], "timed out after 2.00s");
+        assert_eq!(json[2]["mean_ns"], Value::Null);
+    }
+
+    #[test]
+    fn to_csv() {
+        let expected = "\
+profile,day,variant,status,error,part1,part2,samples,min_ns,median_ns,mean_ns,stddev_ns,parse_ns,part1_ns,part2_ns,allocations,allocated_bytes,peak_bytes
+alice,1,solutions,ok,,142,,1,3000,3000,3000,0,1000,1000,0,2,64,48
+bob,10,optimized,ok,,\"#.\n.#\n\",\"a,\"\"b\"\"\",1,3000,3000,3000,0,,,,,,
+bob,11,solutions,timed out,timed out after 2.00s,,,,,,,,,,,,,
+";
+        assert_eq!(csv(&records()), expected);
+    }
+}
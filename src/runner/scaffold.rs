@@ -0,0 +1,147 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::util::profiles::{self, DATA_DIR};
+
+const REGISTRY: &str = "src/solutions.rs";
+
+const TEMPLATE: &str = r#"pub fn run(_input: &str) -> (usize, usize) {
+    // Stub: replace with the actual solution.
+    (0, 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run;
+
+    #[test]
+    fn sample() {
+        let input = "";
+
+        assert_eq!(run(input), (0, 0));
+    }
+}
+"#;
+
+/// Creates the solution of a new day in the crate at `root`, registers it,
+/// and adds empty input and answer files to every profile.
+/// Returns the files that were created or changed.
+pub fn new_day(root: &Path, day: usize) -> Result<Vec<PathBuf>, String> {
+    let module = format!("day{day:02}");
+    let solution = root.join("src/solutions").join(format!("{module}.rs"));
+    if solution.exists() {
+        return Err(format!("{} already exists", solution.display()));
+    }
+    let registry_path = root.join(REGISTRY);
+    let registry = fs::read_to_string(&registry_path)
+        .map_err(|e| format!("couldn't read {}: {e}", registry_path.display()))?;
+    let registry = register(&registry, day).ok_or_else(|| {
+        format!("couldn't register day {day} in {REGISTRY}, is it already there?")
+    })?;
+
+    write(&solution, TEMPLATE)?;
+    // Don't leave an unregistered solution behind, or the next attempt fails on it.
+    if let Err(e) = write(&registry_path, &registry) {
+        let _ = fs::remove_file(&solution);
+        return Err(e);
+    }
+    let mut res = vec![solution, registry_path];
+
+    // A fresh checkout has no data directory, which is fine for the solution itself.
+    let profiles = profiles::in_dir(&root.join(DATA_DIR)).unwrap_or_default();
+    for profile in profiles {
+        for path in [profile.input_path(day), profile.answers_path(day)] {
+            if !path.exists() {
+                let dir = path.parent().expect("data files are in a directory");
+                fs::create_dir_all(dir)
+                    .map_err(|e| format!("couldn't create {}: {e}", dir.display()))?;
+                write(&path, "")?;
+                res.push(path);
+            }
+        }
+    }
+    Ok(res)
+}
+
+fn write(path: &Path, contents: &str) -> Result<(), String> {
+    fs::write(path, contents).map_err(|e| format!("couldn't write {}: {e}", path.display()))
+}
+
+/// Adds `day` to the `register!` invocation in `registry`, either by uncommenting
+/// its placeholder or by inserting it in order.
+/// Returns `None` if the day is already registered or there's no invocation.
+fn register(registry: &str, day: usize) -> Option<String> {
+    let entry = format!("{day} => day{day:02},");
+    let mut lines: Vec<String> = registry.lines().map(str::to_owned).collect();
+    let start = lines.iter().position(|l| l.contains("register! {"))?;
+    let end = start + lines[start..].iter().position(|l| l.trim() == "}")?;
+
+    for idx in start + 1..end {
+        let line = lines[idx].trim();
+        let commented = line.starts_with("//");
+        let registered = line.trim_start_matches("//").trim_start();
+        let registered = registered
+            .split(" =>")
+            .next()
+            .and_then(|d| d.parse::<usize>().ok());
+        match registered {
+            Some(d) if d == day && commented => lines[idx] = format!("    {entry}"),
+            Some(d) if d == day => return None,
+            Some(d) if d > day => lines.insert(idx, format!("    {entry}")),
+            _ => continue,
+        }
+        return Some(lines.join("\n") + "\n");
+    }
+    lines.insert(end, format!("    {entry}"));
+    Some(lines.join("\n") + "\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+crate::registry::register! {
+    1 => day01,
+    9 => day09 (phased),
+    //19 => day19,
+    //20 => day20,
+}
+
+pub mod day08;
+";
+
+    #[test]
+    fn registers() {
+        let registry = register(REGISTRY, 19).unwrap();
+        assert!(registry.contains("    9 => day09 (phased),\n    19 => day19,\n    //20 => day20,"));
+        let registry = register(REGISTRY, 4).unwrap();
+        assert!(registry.contains("    1 => day01,\n    4 => day04,\n    9 => day09"));
+        let registry = register(REGISTRY, 12).unwrap();
+        assert!(registry.contains("(phased),\n    12 => day12,\n    //19"));
+        assert_eq!(register(REGISTRY, 9), None);
+        assert_eq!(register(&register(REGISTRY, 20).unwrap(), 20), None);
+        assert_eq!(register("", 3), None);
+    }
+
+    #[test]
+    fn scaffolds() {
+        let root = std::env::temp_dir().join(format!("aoc2023-scaffold-{}", std::process::id()));
+        fs::create_dir_all(root.join("src/solutions")).unwrap();
+        fs::create_dir_all(root.join("data/alice/inputs")).unwrap();
+        fs::write(root.join(super::REGISTRY), REGISTRY).unwrap();
+
+        let created = new_day(&root, 19).unwrap();
+        assert_eq!(created.len(), 4);
+        assert!(root.join("data/alice/answers/day19.txt").exists());
+        assert_eq!(
+            fs::read_to_string(root.join("src/solutions/day19.rs")).unwrap(),
+            TEMPLATE
+        );
+        assert!(new_day(&root, 19).unwrap_err().contains("already exists"));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
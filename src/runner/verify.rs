@@ -0,0 +1,43 @@
+use crate::util::answer::Answer;
+
+/// Whether an answer matches the known correct one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    /// There is no known answer to compare against.
+    Unknown,
+}
+
+impl Verdict {
+    pub fn check(expected: Option<&Answer>, actual: &Answer) -> Self {
+        match expected {
+            Some(expected) if expected == actual => Self::Correct,
+            Some(_) => Self::Wrong,
+            None => Self::Unknown,
+        }
+    }
+
+    pub fn marker(self) -> &'static str {
+        match self {
+            Self::Correct => "✓",
+            Self::Wrong => "✗",
+            Self::Unknown => "?",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verdicts() {
+        let check = |expected: Option<&str>, actual: u32| {
+            Verdict::check(expected.map(Answer::parse).as_ref(), &actual.into())
+        };
+        assert_eq!(check(Some("142\n"), 142), Verdict::Correct);
+        assert_eq!(check(Some("281"), 280), Verdict::Wrong);
+        assert_eq!(check(None, 50), Verdict::Unknown);
+    }
+}
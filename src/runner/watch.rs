@@ -0,0 +1,271 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    time::{Duration, SystemTime},
+};
+
+use serde_json::Value;
+
+use crate::{
+    runner::args::Args,
+    util::{io::clear, profiles},
+};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Answers of a single variant on a single profile, or why there are none.
+type Outcome = Result<[Option<String>; 2], String>;
+
+/// Outcomes keyed by day, profile and variant.
+type Outcomes = BTreeMap<(usize, String, String), Outcome>;
+
+/// Polls the sources and inputs of the selected days, and reruns the days that change.
+///
+/// The binary can't reload itself, so every rerun rebuilds it with cargo
+/// and runs the new binary, reading its answers from the JSON output.
+pub fn watch(args: &Args) -> Result<(), String> {
+    // Cargo replaces the binary while we're running, so find it before that happens.
+    let exe = std::env::current_exe().map_err(|e| format!("couldn't find the runner: {e}"))?;
+    let days: BTreeSet<usize> = (1..=25).filter(|day| args.has_day(*day)).collect();
+
+    let mut files = snapshot();
+    let mut previous = Outcomes::new();
+    let mut changed = days.clone();
+    loop {
+        if !changed.is_empty() {
+            clear();
+            let list: Vec<_> = changed.iter().map(|day| format!("{day:02}")).collect();
+            println!("Running day {}...\n", list.join(", "));
+            if build()? {
+                match run(&exe, args, &changed) {
+                    Ok(outcomes) => {
+                        print_diff(&previous, &outcomes);
+                        previous.retain(|(day, ..), _| !changed.contains(day));
+                        previous.extend(outcomes);
+                    }
+                    Err(e) => println!("error: {e}"),
+                }
+            }
+            println!("\nWatching for changes, press Ctrl-C to stop.");
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+        let current = snapshot();
+        changed = changed_days(&files, &current)
+            .intersection(&days)
+            .copied()
+            .collect();
+        files = current;
+    }
+}
+
+/// Modification times of the source files and the inputs and answers of every profile.
+fn snapshot() -> HashMap<PathBuf, SystemTime> {
+    let mut res = HashMap::new();
+    let mut dirs = vec![PathBuf::from("src")];
+    if let Ok(profiles) = profiles::all() {
+        for profile in profiles {
+            dirs.push(profile.path.join("inputs"));
+            dirs.push(profile.path.join("answers"));
+        }
+    }
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                dirs.push(path);
+            } else if let Ok(modified) = metadata.modified() {
+                res.insert(path, modified);
+            }
+        }
+    }
+    res
+}
+
+/// Days affected by the files that were added, removed or modified between two snapshots.
+fn changed_days(
+    old: &HashMap<PathBuf, SystemTime>,
+    new: &HashMap<PathBuf, SystemTime>,
+) -> BTreeSet<usize> {
+    let added_or_modified = new
+        .iter()
+        .filter(|(path, time)| old.get(*path) != Some(time));
+    let removed = old.keys().filter(|path| !new.contains_key(*path));
+    let mut res = BTreeSet::new();
+    for path in added_or_modified.map(|(path, _)| path).chain(removed) {
+        match day_of(path) {
+            Some(day) => {
+                res.insert(day);
+            }
+            // Shared code can affect any day.
+            None if path.extension().is_some_and(|ext| ext == "rs") => res.extend(1..=25),
+            None => {}
+        }
+    }
+    res
+}
+
+/// The day of a file named like `day05.rs` or `day05.txt`.
+fn day_of(path: &Path) -> Option<usize> {
+    let stem = path.file_stem()?.to_str()?;
+    stem.strip_prefix("day")?.parse().ok()
+}
+
+/// Rebuilds the runner with the same profile as the one that is running.
+/// Returns `false` if the build failed, after cargo has printed the errors.
+fn build() -> Result<bool, String> {
+    let mut cargo = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_owned()));
+    cargo.args(["build", "--quiet", "--bin", "aoc2023"]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    let status = cargo
+        .status()
+        .map_err(|e| format!("couldn't run cargo: {e}"))?;
+    Ok(status.success())
+}
+
+/// Runs the given days with the rebuilt runner.
+fn run(exe: &Path, args: &Args, days: &BTreeSet<usize>) -> Result<Outcomes, String> {
+    let days: Vec<_> = days.iter().map(usize::to_string).collect();
+    let mut child = Command::new(exe);
+    child.args(["--format", "json", "--day", &days.join(",")]);
+    for profile in &args.profiles {
+        child.args(["--profile", profile]);
+    }
+    if let Some(part) = args.part {
+        child.args(["--part", &part.to_string()]);
+    }
+    if let Some(timeout) = args.timeout {
+        child.args(["--timeout", &timeout.as_secs_f64().to_string()]);
+    }
+    if args.jobs > 1 {
+        child.args(["--jobs", &args.jobs.to_string()]);
+    }
+    // Failures and wrong answers are reported on stderr, so pass it through.
+    let output = child
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|e| format!("couldn't run {}: {e}", exe.display()))?;
+    let json: Value = serde_json::from_slice(&output.stdout)
+        .map_err(|e| format!("couldn't parse the output of the runner: {e}"))?;
+    parse_outcomes(&json).ok_or_else(|| "unexpected output from the runner".to_owned())
+}
+
+fn parse_outcomes(json: &Value) -> Option<Outcomes> {
+    let mut res = Outcomes::new();
+    for record in json.as_array()? {
+        let key = (
+            record["day"].as_u64()? as usize,
+            record["profile"].as_str()?.to_owned(),
+            record["variant"].as_str()?.to_owned(),
+        );
+        let outcome = match record["error"].as_str() {
+            Some(error) => Err(error.to_owned()),
+            None => Ok([answer(&record["part1"]), answer(&record["part2"])]),
+        };
+        res.insert(key, outcome);
+    }
+    Some(res)
+}
+
+/// Renders an answer from the JSON output, where pictures are arrays of rows.
+fn answer(value: &Value) -> Option<String> {
+    match value {
+        Value::Null => None,
+        Value::String(s) => Some(s.clone()),
+        Value::Array(rows) => {
+            let rows: Vec<_> = rows.iter().filter_map(Value::as_str).collect();
+            Some(rows.join("\n"))
+        }
+        value => Some(value.to_string()),
+    }
+}
+
+/// Lists the new answers, showing the old ones next to those that changed.
+fn print_diff(previous: &Outcomes, outcomes: &Outcomes) {
+    for line in diff(previous, outcomes) {
+        println!("{line}");
+    }
+}
+
+fn diff(previous: &Outcomes, outcomes: &Outcomes) -> Vec<String> {
+    let mut res = Vec::new();
+    for (key @ (day, profile, variant), outcome) in outcomes {
+        let name = format!("Day {day:02} {profile} ({variant})");
+        let answers = match outcome {
+            Ok(answers) => answers,
+            Err(error) => {
+                res.push(format!("{name}: {error}"));
+                continue;
+            }
+        };
+        let old = match previous.get(key) {
+            Some(Ok(old)) => Some(old),
+            _ => None,
+        };
+        for (part, answer) in answers.iter().enumerate() {
+            let Some(answer) = answer else {
+                continue;
+            };
+            let part = part + 1;
+            match old.and_then(|old| old[part - 1].as_ref()) {
+                Some(old) if old != answer => {
+                    res.push(format!("{name}, part {part}: {old} -> {answer} (changed)"));
+                }
+                _ => res.push(format!("{name}, part {part}: {answer}")),
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn days() {
+        let t = |secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let old = HashMap::from([
+            (PathBuf::from("src/solutions/day05.rs"), t(1)),
+            (PathBuf::from("data/alice/inputs/day07.txt"), t(1)),
+            (PathBuf::from("data/alice/inputs/day09.txt"), t(1)),
+        ]);
+        let mut new = old.clone();
+        new.insert(PathBuf::from("src/solutions/day05.rs"), t(2));
+        new.remove(Path::new("data/alice/inputs/day07.txt"));
+        new.insert(PathBuf::from("data/bob/inputs/day11.txt"), t(2));
+        assert_eq!(changed_days(&old, &new), BTreeSet::from([5, 7, 11]));
+
+        new.insert(PathBuf::from("src/util/grid.rs"), t(2));
+        assert_eq!(changed_days(&old, &new).len(), 25);
+    }
+
+    #[test]
+    fn diffs() {
+        let json = serde_json::json!([
+            {"day": 5, "profile": "alice", "variant": "solutions", "error": null, "part1": 35, "part2": 46},
+            {"day": 6, "profile": "alice", "variant": "solutions", "error": "panicked: oops", "part1": null, "part2": null},
+        ]);
+        let new = parse_outcomes(&json).unwrap();
+        let key = (5, String::from("alice"), String::from("solutions"));
+        let old = Outcomes::from([(
+            key,
+            Ok([Some(String::from("35")), Some(String::from("45"))]),
+        )]);
+        let expected = [
+            "Day 05 alice (solutions), part 1: 35",
+            "Day 05 alice (solutions), part 2: 45 -> 46 (changed)",
+            "Day 06 alice (solutions): panicked: oops",
+        ];
+        assert_eq!(diff(&old, &new), expected);
+    }
+}
@@ -1,25 +1,29 @@
-pub mod day01;
-pub mod day02;
-pub mod day03;
-pub mod day04;
-pub mod day05;
-pub mod day06;
-pub mod day07;
+crate::registry::register! {
+    1 => day01,
+    2 => day02,
+    3 => day03,
+    4 => day04,
+    5 => day05,
+    6 => day06,
+    7 => day07,
+    9 => day09 (phased),
+    10 => day10,
+    11 => day11,
+    12 => day12,
+    13 => day13,
+    14 => day14,
+    15 => day15,
+    16 => day16,
+    17 => day17,
+    18 => day18,
+    //19 => day19,
+    //20 => day20,
+    //21 => day21,
+    //22 => day22,
+    //23 => day23,
+    //24 => day24,
+    //25 => day25,
+}
+
+// Part 2 doesn't terminate on real inputs yet, so it isn't registered.
 pub mod day08;
-pub mod day09;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
-pub mod day16;
-pub mod day17;
-pub mod day18;
-//pub mod day19;
-//pub mod day20;
-//pub mod day21;
-//pub mod day22;
-//pub mod day23;
-//pub mod day24;
-//pub mod day25;
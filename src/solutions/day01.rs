@@ -1,14 +1,16 @@
-pub fn run(input: &str) -> (u32, u32) {
-    let part1 = input
-        .lines()
-        .map(|l| {
-            let mut bytes = l.as_bytes().iter();
-            let x = bytes.find(|b| b.is_ascii_digit()).unwrap();
-            let mut bytes = l.as_bytes().iter();
-            let y = bytes.rfind(|b| b.is_ascii_digit()).unwrap();
-            ((x - b'0') * 10 + y - b'0') as u32
-        })
-        .sum();
+use crate::util::parsers::ParseError;
+
+pub fn run(input: &str) -> Result<(u32, u32), ParseError> {
+    let mut part1 = 0;
+    for l in input.lines() {
+        let mut bytes = l.as_bytes().iter();
+        let Some(x) = bytes.find(|b| b.is_ascii_digit()) else {
+            return Err(ParseError::new(input, l, "line without a digit"));
+        };
+        let mut bytes = l.as_bytes().iter();
+        let y = bytes.rfind(|b| b.is_ascii_digit()).unwrap();
+        part1 += ((x - b'0') * 10 + y - b'0') as u32;
+    }
 
     let mut part2 = 0;
     for mut line in input.lines() {
@@ -31,11 +33,10 @@ pub fn run(input: &str) -> (u32, u32) {
         part2 += x * 10 + y;
     }
 
-    (part1, part2)
+    Ok((part1, part2))
 }
 
-const NUMBERS: [(&str, u32); 20] = [
-    ("zero", 0),
+const NUMBERS: [(&str, u32); 19] = [
     ("one", 1),
     ("two", 2),
     ("three", 3),
@@ -2,9 +2,14 @@ use std::{collections::HashMap, ops::Range};
 
 use itertools::Itertools;
 
-pub fn run(input: &str) -> (u32, u32) {
+use crate::util::parsers::{self, ParseError};
+
+pub fn run(input: &str) -> Result<(u32, u32), ParseError> {
     // Parse
-    let mut rows = input.lines().map(parse_line).collect_vec();
+    let mut rows: Vec<_> = input
+        .lines()
+        .map(|line| parse_line(input, line))
+        .try_collect()?;
 
     // Add padding
     rows.push(Vec::new());
@@ -67,10 +72,10 @@ pub fn run(input: &str) -> (u32, u32) {
         }
         part2 += gear.iter().map(|n| n.2).product::<u32>();
     }
-    (part1, part2)
+    Ok((part1, part2))
 }
 
-fn parse_line(line: &str) -> Vec<Elem> {
+fn parse_line(input: &str, line: &str) -> Result<Vec<Elem>, ParseError> {
     let mut bytes = line.bytes().enumerate().peekable();
     let mut summary = Vec::new();
     while let Some((j, byte)) = bytes.next() {
@@ -78,18 +83,16 @@ fn parse_line(line: &str) -> Vec<Elem> {
             b'.' => continue,
             d if d.is_ascii_digit() => {
                 let mut end = j;
-                let mut value = (d - b'0') as u32;
                 while bytes.peek().map(|b| b.1.is_ascii_digit()) == Some(true) {
-                    let (c, next_d) = bytes.next().unwrap();
-                    end = c;
-                    value = value * 10 + (next_d - b'0') as u32;
+                    end = bytes.next().unwrap().0;
                 }
+                let value = parsers::parse(input, &line[j..=end], "part number")?;
                 summary.push(Elem::number(value, j, end));
             }
             symbol => summary.push(Elem::symbol(j, symbol == b'*')),
         }
     }
-    summary
+    Ok(summary)
 }
 
 #[derive(Debug)]
@@ -129,53 +132,3 @@ struct Number {
     range: Range<usize>,
     value: u32,
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "467..114..
-...*......
-..35..633.
-......#...
-617*......
-.....+.58.
-..592.....
-......755.
-...$.*....
-.664.598..";
-        assert_eq!(super::run(input), (4361, 467835));
-    }
-
-    #[test]
-    fn corner_cases() {
-        let input = "....................
-.......100..........
-......*.............
-..........'.........
-...........100......
-....100.............
-.......,...100......
-..100.......'.......
-.....-..............
-..100...............
-........100&........
-.......&100.........
-.................100
-..............100...
-100.................
-g...................
-...-....-...........
-..5......5....-5....
-....................
-...5.....5....5-....
-....-...-...........
-..............-.....
-....5.........5.....
-....-...............
-.........3........-.
-..........&100...5..
-";
-        assert_eq!(super::run(input).0, 1048);
-    }
-}
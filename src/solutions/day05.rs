@@ -1,14 +1,24 @@
 use itertools::Itertools;
 
-use crate::util::parsers::Numbers;
-
-pub fn run(input: &str) -> (u64, u64) {
-    let (seeds, tail) = input.split_once("\n\n").unwrap();
-    let maps: Vec<Map> = tail.split("\n\n").map(Map::parse).collect_vec();
+use crate::util::parsers::{self, ParseError};
+
+pub fn run(input: &str) -> Result<(u64, u64), ParseError> {
+    let Some((seeds, tail)) = input.split_once("\n\n") else {
+        return Err(ParseError::new(input, &input[input.len()..], "missing maps"));
+    };
+    let seeds: Vec<u64> = seeds
+        .split(' ')
+        .dropping(1)
+        .map(|seed| parsers::parse(input, seed, "seed"))
+        .try_collect()?;
+    let maps: Vec<Map> = tail
+        .split("\n\n")
+        .map(|map| Map::parse(input, map))
+        .try_collect()?;
 
     let mut part1 = u64::MAX;
-    for seed in seeds.split(' ').dropping(1) {
-        let mut r = seed.parse::<u64>().unwrap();
+    for &seed in &seeds {
+        let mut r = seed;
         for map in &maps {
             r = map.map(r);
         }
@@ -16,9 +26,7 @@ pub fn run(input: &str) -> (u64, u64) {
     }
 
     let mut part2 = u64::MAX;
-    for (start, len) in seeds.split(' ').dropping(1).tuples() {
-        let s = start.parse::<u64>().unwrap();
-        let n = len.parse::<u64>().unwrap();
+    for (&s, &n) in seeds.iter().tuples() {
         let mut ranges = vec![(s, s + n)];
         for map in &maps {
             ranges = ranges
@@ -26,10 +34,12 @@ pub fn run(input: &str) -> (u64, u64) {
                 .flat_map(|range| map.map_range(*range))
                 .collect();
         }
-        part2 = part2.min(ranges.iter().map(|r| r.0).min().unwrap());
+        if let Some(min) = ranges.iter().map(|r| r.0).min() {
+            part2 = part2.min(min);
+        }
     }
 
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 struct Map {
@@ -44,14 +54,24 @@ struct MapRange {
 }
 
 impl Map {
-    fn parse(input: &str) -> Self {
-        let (_, tail) = input.split_once('\n').unwrap();
-        let mut ranges = Numbers::new(tail)
-            .tuples()
-            .map(|(dst, src, len)| MapRange { src, dst, len })
-            .collect_vec();
+    /// Parses a map section of `input`, a title line followed by one range per line.
+    fn parse(input: &str, section: &str) -> Result<Self, ParseError> {
+        let Some((_, tail)) = section.split_once('\n') else {
+            return Err(ParseError::new(input, section, "map without ranges"));
+        };
+        let mut ranges = Vec::new();
+        for line in tail.lines() {
+            let Some((dst, src, len)) = line.split_ascii_whitespace().collect_tuple() else {
+                return Err(ParseError::new(input, line, "invalid range"));
+            };
+            ranges.push(MapRange {
+                src: parsers::parse(input, src, "range start")?,
+                dst: parsers::parse(input, dst, "range start")?,
+                len: parsers::parse(input, len, "range length")?,
+            });
+        }
         ranges.sort_by_key(|r| r.src);
-        Map { ranges }
+        Ok(Map { ranges })
     }
 
     fn map(&self, x: u64) -> u64 {
@@ -110,45 +130,3 @@ impl Map {
         res
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "seeds: 79 14 55 13
-
-seed-to-soil map:
-50 98 2
-52 50 48
-
-soil-to-fertilizer map:
-0 15 37
-37 52 2
-39 0 15
-
-fertilizer-to-water map:
-49 53 8
-0 11 42
-42 0 7
-57 7 4
-
-water-to-light map:
-88 18 7
-18 25 70
-
-light-to-temperature map:
-45 77 23
-81 45 19
-68 64 13
-
-temperature-to-humidity map:
-0 69 1
-1 0 69
-
-humidity-to-location map:
-60 56 37
-56 93 4";
-
-        assert_eq!(super::run(input), (35, 46));
-    }
-}
@@ -1,28 +1,56 @@
 use itertools::Itertools;
 
-use crate::util::parsers::Numbers;
+use crate::util::parsers::{self, ParseError};
 
-pub fn run(input: &str) -> (u64, u64) {
-    // Parse
-    let (times, dists) = input.split_once('\n').unwrap();
-    let times: Vec<u64> = Numbers::new(times).collect();
-    let dists: Vec<u64> = Numbers::new(dists).collect();
+pub fn run(input: &str) -> Result<(u64, u64), ParseError> {
+    let (races, (time, dist)) = parse(input)?;
 
     // Part 1
     let mut part1 = 1;
-    for (time, dist) in times.iter().zip(dists.iter()) {
-        part1 *= ways_to_beat(*time, *dist);
+    for (time, dist) in races {
+        part1 *= ways_to_beat(time, dist);
     }
 
     // Part 2
-    let input: String = input
-        .chars()
-        .filter(|x| x.is_ascii_digit() || *x == '\n')
-        .collect();
-    let (time, dist): (u64, u64) = Numbers::new(&input).collect_tuple().unwrap();
     let part2 = ways_to_beat(time, dist);
 
-    (part1, part2)
+    Ok((part1, part2))
+}
+
+/// The time and record distance of a race.
+pub type Race = (u64, u64);
+
+/// Parses each race, and the single race with all of their digits joined for part 2.
+pub fn parse(input: &str) -> Result<(Vec<Race>, Race), ParseError> {
+    let (time_line, dist_line) = input
+        .split_once('\n')
+        .unwrap_or((input, &input[input.len()..]));
+    let (times, time) = numbers(input, time_line, "time")?;
+    let (dists, dist) = numbers(input, dist_line, "distance")?;
+    if times.len() != dists.len() {
+        let line = dist_line.lines().next().unwrap_or(dist_line);
+        let message = format!("expected {} distances, found {} in", times.len(), dists.len());
+        return Err(ParseError::new(input, line, message));
+    }
+    Ok((times.into_iter().zip(dists).collect(), (time, dist)))
+}
+
+/// Parses the numbers after the label of `line`, fx. `Time:  7  15`,
+/// and the number formed by joining them.
+fn numbers(input: &str, line: &str, what: &str) -> Result<(Vec<u64>, u64), ParseError> {
+    let line = line.lines().next().unwrap_or(line);
+    let texts = line.split_ascii_whitespace().skip(1).collect_vec();
+    if texts.is_empty() {
+        return Err(ParseError::new(input, &line[line.len()..], format!("missing {what}")));
+    }
+    let numbers = texts
+        .iter()
+        .map(|text| parsers::parse(input, text, what))
+        .try_collect()?;
+    let joined = texts.concat().parse().map_err(|_| {
+        ParseError::new(input, line, format!("joined {what} is too large in"))
+    })?;
+    Ok((numbers, joined))
 }
 
 fn ways_to_beat(time: u64, dist: u64) -> u64 {
@@ -1,14 +1,16 @@
 use std::{collections::HashMap, marker::PhantomData};
 
-use itertools::Itertools;
+use crate::util::parsers::{self, ParseError};
 
-pub fn run(input: &str) -> (u32, u32) {
+pub fn run(input: &str) -> Result<(u32, u32), ParseError> {
     let mut deals1 = Vec::new();
     let mut deals2 = Vec::new();
-    for (hand, bid) in input.split_ascii_whitespace().tuples() {
-        let hand1 = Hand::<One>::parse(hand.as_bytes().try_into().unwrap(), b"23456789TJQKA");
-        let hand2 = Hand::<Two>::parse(hand.as_bytes().try_into().unwrap(), b"J23456789TQKA");
-        let bid = bid.parse::<u32>().unwrap();
+    for line in input.lines() {
+        let (hand, bid) = line.split_once(' ').unwrap_or((line, &line[line.len()..]));
+        let cards = parse_cards(input, hand)?;
+        let hand1 = Hand::<One>::parse(cards, b"23456789TJQKA");
+        let hand2 = Hand::<Two>::parse(cards, b"J23456789TQKA");
+        let bid = parsers::parse::<u32>(input, bid, "bid")?;
         deals1.push((hand1, bid));
         deals2.push((hand2, bid));
     }
@@ -27,7 +29,16 @@ pub fn run(input: &str) -> (u32, u32) {
         .map(|(i, x)| (i + 1) as u32 * x.1)
         .sum();
 
-    (part1, part2)
+    Ok((part1, part2))
+}
+
+fn parse_cards<'a>(input: &str, hand: &'a str) -> Result<&'a [u8; 5], ParseError> {
+    if let Some((i, c)) = hand.char_indices().find(|(_, c)| !"23456789TJQKA".contains(*c)) {
+        return Err(ParseError::new(input, &hand[i..i + c.len_utf8()], "invalid card"));
+    }
+    hand.as_bytes()
+        .try_into()
+        .map_err(|_| ParseError::new(input, hand, "invalid hand"))
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
@@ -60,6 +71,7 @@ struct One;
 struct Two;
 
 impl<P> Hand<P> {
+    /// Parses cards that are known to be valid.
     fn parse(cards: &[u8; 5], ranking: &[u8]) -> Self {
         Self(
             PhantomData,
@@ -162,16 +174,3 @@ impl Ord for Hand<Two> {
         (self.kind2(), self.1).cmp(&(other.kind2(), other.1))
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "32T3K 765
-T55J5 684
-KK677 28
-KTJJT 220
-QQQJA 483";
-        assert_eq!(super::run(input), (6440, 5905));
-    }
-}
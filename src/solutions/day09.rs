@@ -1,17 +1,24 @@
 use itertools::Itertools;
 
-use crate::util::parsers::Numbers;
+use crate::util::parsers::{self, ParseError};
 
-pub fn run(input: &str) -> (i32, i32) {
-    let mut part1 = 0;
-    let mut part2 = 0;
-    for line in input.lines() {
-        let sequence = Numbers::new(line).collect_vec();
-        let (first, last) = task(&sequence);
-        part1 += last;
-        part2 += first;
-    }
-    (part1, part2)
+pub fn parse(input: &str) -> Result<Vec<Vec<i32>>, ParseError> {
+    input
+        .lines()
+        .map(|line| {
+            line.split_ascii_whitespace()
+                .map(|n| parsers::parse(input, n, "number"))
+                .collect()
+        })
+        .collect()
+}
+
+pub fn part1(sequences: &[Vec<i32>]) -> i32 {
+    sequences.iter().map(|sequence| task(sequence).1).sum()
+}
+
+pub fn part2(sequences: &[Vec<i32>]) -> i32 {
+    sequences.iter().map(|sequence| task(sequence).0).sum()
 }
 
 fn task(data: &[i32]) -> (i32, i32) {
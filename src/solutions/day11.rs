@@ -1,15 +1,18 @@
-use crate::util::grid::{Grid, Pos, Line, pos};
+use crate::util::{
+    grid::{pos, Grid, Pos},
+    parsers::ParseError,
+};
 
-pub fn run(input: &str) -> (u32, u64) {
+pub fn run(input: &str) -> Result<(u32, u64), ParseError> {
     let mut galaxies = Vec::new();
-    let grid = Grid::parse(input, |p, c| match c {
-        '.' => false,
+    let grid = Grid::try_parse(input, "pixel", |p, c| match c {
+        '.' => Some(false),
         '#' => {
             galaxies.push(p);
-            true
+            Some(true)
         }
-        _ => panic!(),
-    });
+        _ => None,
+    })?;
 
     let mut vert_lines = vec![false; grid.width()];
     let mut hori_lines = vec![false; grid.height()];
@@ -45,5 +48,5 @@ pub fn run(input: &str) -> (u32, u64) {
     part1 /= 2;
     part2 /= 2;
 
-    (part1, part2)
+    Ok((part1, part2))
 }
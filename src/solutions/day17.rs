@@ -3,12 +3,13 @@ use std::{ops::IndexMut, vec::IntoIter};
 use crate::util::{
     graph::{Graph, GraphImpl},
     grid::{pos, Dir4, Grid, Pos, Rot},
+    parsers::ParseError,
     pathfinding::dijkstra,
     queue::SlidingBucketQueue,
 };
 
-pub fn run(input: &str) -> (usize, usize) {
-    let grid = Grid::parse(input, |_, c| c.to_digit(10).unwrap() as u8);
+pub fn run(input: &str) -> Result<(usize, usize), ParseError> {
+    let grid = Grid::try_parse(input, "heat loss", |_, c| c.to_digit(10).map(|d| d as u8))?;
     let get_edge = |(_, pa): (Dir4, Pos), (_, pb): (Dir4, Pos)| {
         let mut line = pa.line(&pb).unwrap();
         line.next();
@@ -56,7 +57,7 @@ pub fn run(input: &str) -> (usize, usize) {
     )
     .unwrap();
 
-    (part1_a.min(part1_b), part2_a.min(part2_b))
+    Ok((part1_a.min(part1_b), part2_a.min(part2_b)))
 }
 
 // The challenge here is,
@@ -189,24 +190,3 @@ impl<T> GraphImpl<T> for City2<T> {
         }
     }
 }
-
-#[cfg(test)]
-mod tests {
-    #[test]
-    fn sample() {
-        let input = "2413432311323
-3215453535623
-3255245654254
-3446585845452
-4546657867536
-1438598798454
-4457876987766
-3637877979653
-4654967986887
-4564679986453
-1224686865563
-2546548887735
-4322674655533";
-        assert_eq!(super::run(input), (102, 94));
-    }
-}
@@ -1,81 +1,76 @@
-use std::fmt::{Display, Write};
-
 use itertools::Itertools;
 
 use crate::util::{
-    grid::{pos, Dir4, Grid},
-    math::gcd,
+    grid::{pos, Dir4, IPos},
+    infinite_grid::InfiniteGrid,
+    parsers::{self, ParseError},
     pathfinding::bfs,
 };
 
-pub fn run(input: &str) -> (usize, usize) {
+pub fn run(input: &str) -> Result<(usize, usize), ParseError> {
+    let mut insts = Vec::new();
     let mut insts2 = Vec::new();
-    let insts = input
-        .lines()
-        .map(|l| {
-            let (dir, len, color) = l.split_ascii_whitespace().collect_tuple().unwrap();
-            let dir = match dir {
-                "U" => Dir4::N,
-                "D" => Dir4::S,
-                "L" => Dir4::W,
-                "R" => Dir4::E,
-                _ => panic!(),
-            };
-            let len = len.parse::<usize>().unwrap();
-            let dir2 = match color.chars().nth(7).unwrap() {
-                '0' => Dir4::E,
-                '1' => Dir4::S,
-                '2' => Dir4::W,
-                '3' => Dir4::N,
-                _ => panic!(),
-            };
-            let len2 = usize::from_str_radix(&color[2..7], 16).unwrap();
-            insts2.push((dir2, len2));
-            (dir, len)
-        })
-        .collect_vec();
+    for l in input.lines() {
+        let Some((dir, len, color)) = l.split_ascii_whitespace().collect_tuple() else {
+            return Err(ParseError::new(input, l, "invalid instruction"));
+        };
+        let dir = match dir {
+            "U" => Dir4::N,
+            "D" => Dir4::S,
+            "L" => Dir4::W,
+            "R" => Dir4::E,
+            _ => return Err(ParseError::new(input, dir, "invalid direction")),
+        };
+        let len = parsers::parse::<usize>(input, len, "length")?;
+        let Some((len2, dir2)) = color
+            .strip_prefix("(#")
+            .and_then(|c| c.strip_suffix(')'))
+            .filter(|c| c.len() == 6 && c.is_ascii())
+            .map(|c| c.split_at(5))
+        else {
+            return Err(ParseError::new(input, color, "invalid color"));
+        };
+        let dir2 = match dir2 {
+            "0" => Dir4::E,
+            "1" => Dir4::S,
+            "2" => Dir4::W,
+            "3" => Dir4::N,
+            _ => return Err(ParseError::new(input, color, "invalid color")),
+        };
+        let Ok(len2) = usize::from_str_radix(len2, 16) else {
+            return Err(ParseError::new(input, color, "invalid color"));
+        };
+        insts.push((dir, len));
+        insts2.push((dir2, len2));
+    }
+    if insts.is_empty() {
+        return Err(ParseError::new(input, &input[..0], "missing instructions"));
+    }
 
     let part1 = solve(insts);
     let part2 = solve(insts2);
 
     // Time to make a 2-dimensional range map again?
-    (part1, part2)
+    Ok((part1, part2))
 }
 
 fn solve(insts: Vec<(Dir4, usize)>) -> usize {
-    let (mut x, mut y) = (0i64, 0i64);
-    let (mut min_x, mut max_x, mut min_y, mut max_y) = (0, 0, 0, 0);
-
-    for (d, n) in &insts {
-        match d {
-            Dir4::N => y -= *n as i64,
-            Dir4::E => x += *n as i64,
-            Dir4::S => y += *n as i64,
-            Dir4::W => x -= *n as i64,
-        }
-        min_x = min_x.min(x);
-        max_x = max_x.max(x);
-        min_y = min_y.min(y);
-        max_y = max_y.max(y);
-    }
-
-    let width = (max_x - min_x + 1) as usize;
-    let height = (max_y - min_y + 1) as usize;
-    let mut grid = Grid::new_filled(width, height, false);
-
+    let mut trench = InfiniteGrid::new(false);
     let mut res = 0;
-    let mut p = pos(-min_x as usize, -min_y as usize);
-    for (d, mut n) in insts {
-        while n > 0 {
+    let mut p = IPos::ZERO;
+    for (d, n) in insts {
+        for _ in 0..n {
             res += 1;
-            grid[p] = true;
+            trench[p] = true;
             p = p.step(d);
-            n -= 1;
         }
     }
+    let Some((grid, corner)) = trench.to_grid() else {
+        return 0;
+    };
 
     // Identify a side `#.` of the structure, where the `#` is visible from the edge.
-    let mut start = pos(0, -min_y as usize);
+    let mut start = pos(0, -corner.y as usize);
     loop {
         if grid[start] {
             if !grid[start.step(Dir4::E)] {
@@ -102,18 +97,3 @@ fn solve(insts: Vec<(Dir4, usize)>) -> usize {
     );
     res
 }
-
-enum Cell {
-    T,
-    F,
-}
-
-impl Display for Cell {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let c = match self {
-            Cell::T => '#',
-            Cell::F => '.',
-        };
-        f.write_char(c)
-    }
-}
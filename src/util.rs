@@ -1,10 +1,15 @@
+pub mod answer;
 pub mod cycles;
 pub mod dfs;
+pub mod examples;
 pub mod graph;
 pub mod grid;
 pub mod grid3d;
+pub mod infinite_grid;
 pub mod pathfinding;
+pub mod profiles;
 pub mod queue;
 pub mod parsers;
 pub mod io;
 pub mod math;
+pub mod ocr;
@@ -0,0 +1,197 @@
+use std::fmt::Display;
+
+use num_bigint::{BigInt, BigUint};
+use serde_json::Value;
+
+use crate::util::{
+    grid::{pos, BitGrid, Grid},
+    ocr,
+};
+
+/// The answer to a part of a puzzle.
+///
+/// Values are normalized on construction, so that fx. a `u128` and a `BigInt`
+/// with the same value compare equal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    /// Integers that don't fit in an `i128`.
+    Big(BigInt),
+    /// A single line of text.
+    Text(String),
+    /// Rows of a picture that couldn't be read as letters.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Parses an answer the way it is written in an answer file,
+    /// ignoring trailing whitespace.
+    pub fn parse(text: &str) -> Self {
+        let text = text.trim_end();
+        if let Ok(n) = text.parse::<i128>() {
+            Self::Int(n)
+        } else if let Ok(n) = text.parse::<BigInt>() {
+            Self::Big(n)
+        } else {
+            Self::from(text)
+        }
+    }
+
+    /// Whether the answer spans several lines when displayed.
+    pub fn is_multiline(&self) -> bool {
+        matches!(self, Self::Grid(rows) if rows.len() > 1)
+    }
+
+    /// Numbers become JSON numbers when they fit in 64 bits, and grids become arrays of rows.
+    pub fn to_json(&self) -> Value {
+        match self {
+            Self::Int(n) => {
+                if let Ok(n) = i64::try_from(*n) {
+                    Value::from(n)
+                } else if let Ok(n) = u64::try_from(*n) {
+                    Value::from(n)
+                } else {
+                    Value::from(n.to_string())
+                }
+            }
+            Self::Big(n) => Value::from(n.to_string()),
+            Self::Text(s) => Value::from(s.as_str()),
+            Self::Grid(rows) => Value::from(rows.clone()),
+        }
+    }
+
+    /// Reads the letters in a picture, keeping the picture if that fails.
+    fn from_pixels(width: usize, height: usize, pixel: impl Fn(usize, usize) -> bool) -> Self {
+        if let Some(text) = ocr::read(width, height, &pixel) {
+            return Self::Text(text);
+        }
+        let rows = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| if pixel(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        Self::Grid(rows)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Int(n) => n.fmt(f),
+            Self::Big(n) => n.fmt(f),
+            Self::Text(s) => s.fmt(f),
+            Self::Grid(rows) => {
+                for row in rows {
+                    writeln!(f, "{row}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+macro_rules! from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Self::Int(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+from_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize, i128);
+
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        match i128::try_from(n) {
+            Ok(n) => Self::Int(n),
+            Err(_) => Self::Big(n.into()),
+        }
+    }
+}
+
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match i128::try_from(&n) {
+            Ok(n) => Self::Int(n),
+            Err(_) => Self::Big(n),
+        }
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        BigInt::from(n).into()
+    }
+}
+
+/// Text with several lines is read as letters drawn with `#`, or becomes a grid.
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        if s.trim_end().contains('\n') {
+            if let Some(text) = ocr::read_text(s) {
+                return Self::Text(text);
+            }
+            Self::Grid(s.trim_end().lines().map(str::to_owned).collect())
+        } else {
+            Self::Text(s.to_owned())
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl From<&Grid<bool>> for Answer {
+    fn from(grid: &Grid<bool>) -> Self {
+        Self::from_pixels(grid.width(), grid.height(), |x, y| grid[pos(x, y)])
+    }
+}
+
+impl From<&BitGrid> for Answer {
+    fn from(grid: &BitGrid) -> Self {
+        Self::from_pixels(grid.width(), grid.height(), |x, y| {
+            grid.get(pos(x, y)).unwrap()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize() {
+        assert_eq!(Answer::from(5u8), Answer::from(5u128));
+        assert_eq!(Answer::from(BigInt::from(-7)), Answer::Int(-7));
+        let big = Answer::from(u128::MAX);
+        assert!(matches!(big, Answer::Big(_)));
+        assert_eq!(Answer::parse(&format!("{big}\n")), big);
+        assert_eq!(Answer::parse("142\n"), Answer::from(142u32));
+        assert_eq!(Answer::parse("DJGF8ASH\n"), Answer::from("DJGF8ASH"));
+    }
+
+    #[test]
+    fn grids() {
+        let mut grid = Grid::new_filled(2, 2, false);
+        grid[pos(0, 0)] = true;
+        grid[pos(1, 1)] = true;
+        let answer = Answer::from(&grid);
+        assert!(answer.is_multiline());
+        assert_eq!(answer.to_string(), "#.\n.#\n");
+        assert_eq!(Answer::parse("#.\n.#\n"), answer);
+        assert_eq!(answer.to_json(), serde_json::json!(["#.", ".#"]));
+
+        let letters = ".##..###.\n#..#.#..#\n#..#.###.\n####.#..#\n#..#.#..#\n#..#.###.\n";
+        assert_eq!(Answer::from(letters), Answer::parse("AB"));
+        assert_eq!(Answer::parse(letters), Answer::parse("AB"));
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::util::{
+    answer::Answer,
+    profiles::{read_answers, Error, DATA_DIR},
+};
+
+/// Folder in the data directory with the examples from the puzzle descriptions.
+pub const EXAMPLES_DIR: &str = "examples";
+
+const ANSWERS_SUFFIX: &str = ".answers.txt";
+
+/// An example input from a puzzle description, stored next to its answers like this:
+///
+/// ```text
+/// data/examples/day01/<name>.txt
+/// data/examples/day01/<name>.answers.txt
+/// ```
+///
+/// The answers use the same format as the answers of a profile,
+/// so an example that only applies to part 2 starts with an empty line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: usize,
+    pub name: String,
+    pub path: PathBuf,
+}
+
+impl Example {
+    pub fn input(&self) -> Result<String, Error> {
+        std::fs::read_to_string(&self.path).map_err(|e| Error::Io(self.path.clone(), e))
+    }
+
+    pub fn answers_path(&self) -> PathBuf {
+        self.path
+            .with_file_name(format!("{}{ANSWERS_SUFFIX}", self.name))
+    }
+
+    /// Known answers of the example, with `None` for parts that it doesn't apply to.
+    pub fn answers(&self) -> Result<[Option<Answer>; 2], Error> {
+        read_answers(self.answers_path())
+    }
+}
+
+/// Lists the examples in `data/examples/`, sorted by day and name.
+pub fn all() -> Result<Vec<Example>, Error> {
+    in_dir(&Path::new(DATA_DIR).join(EXAMPLES_DIR))
+}
+
+/// Lists the examples in the `dayNN` folders of `dir`, sorted by day and name.
+/// A missing directory has no examples.
+pub fn in_dir(dir: &Path) -> Result<Vec<Example>, Error> {
+    let mut res = Vec::new();
+    for (day, day_dir) in entries(dir)? {
+        let Some(day) = day.strip_prefix("day").and_then(|d| d.parse().ok()) else {
+            continue;
+        };
+        for (file, path) in entries(&day_dir)? {
+            let Some(name) = file.strip_suffix(".txt") else {
+                continue;
+            };
+            if !file.ends_with(ANSWERS_SUFFIX) {
+                res.push(Example {
+                    day,
+                    name: name.to_owned(),
+                    path,
+                });
+            }
+        }
+    }
+    res.sort_by(|a, b| (a.day, &a.name).cmp(&(b.day, &b.name)));
+    Ok(res)
+}
+
+/// Names and paths of the entries in `dir`, or nothing if it doesn't exist.
+fn entries(dir: &Path) -> Result<Vec<(String, PathBuf)>, Error> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Error::Io(dir.to_owned(), e)),
+    };
+    let mut res = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| Error::Io(dir.to_owned(), e))?.path();
+        let Some(name) = path.file_name().unwrap().to_str() else {
+            return Err(Error::InvalidName(path));
+        };
+        res.push((name.to_owned(), path));
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn discovery() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-examples-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(in_dir(&dir).unwrap(), []);
+
+        for day in ["day10", "day03", "notes"] {
+            std::fs::create_dir_all(dir.join(day)).unwrap();
+        }
+        std::fs::write(dir.join("day10/small.txt"), "input").unwrap();
+        std::fs::write(dir.join("day10/small.answers.txt"), "\n\n10\n").unwrap();
+        std::fs::write(dir.join("day03/example.txt"), "input").unwrap();
+        std::fs::write(dir.join("day03/README.md"), "not an example").unwrap();
+        std::fs::write(dir.join("day10/large.txt"), "input").unwrap();
+
+        let examples = in_dir(&dir).unwrap();
+        let names: Vec<_> = examples.iter().map(|e| (e.day, e.name.as_str())).collect();
+        assert_eq!(names, [(3, "example"), (10, "large"), (10, "small")]);
+        assert_eq!(examples[2].input().unwrap(), "input");
+        assert_eq!(
+            examples[2].answers().unwrap(),
+            [None, Some(Answer::Int(10))]
+        );
+        assert_eq!(examples[0].answers().unwrap(), [None, None]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
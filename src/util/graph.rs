@@ -5,9 +5,10 @@ use std::ops::{Index, IndexMut, Range};
 #[rustfmt::skip] pub trait Graph<T> where Self: GraphImpl<T, Map<T> = Self> {}
 
 /// Actual implementation of the graph class.
+/// Only the mapped graphs need to be mutable, so read-only views can implement it too.
 pub trait GraphImpl<T>
 where
-    Self: Index<Self::Node, Output = T> + IndexMut<Self::Node, Output = T>,
+    Self: Index<Self::Node, Output = T>,
 {
     /// A reference to a node in a graph.
     /// This should index the equivalent node between mapped graphs.
@@ -24,7 +25,7 @@ where
 
     /// Mapping function, creates a new graph with the exact same structure.
     fn map<U, F: Copy + FnMut(&T) -> U>(&self, f: F) -> Self::Map<U>;
-    type Map<U>: GraphImpl<U, Node = Self::Node>;
+    type Map<U>: GraphImpl<U, Node = Self::Node> + IndexMut<Self::Node, Output = U>;
 }
 
 /// An index graph backed by a `Vec`.
@@ -1,9 +1,18 @@
 use std::{
     fmt::{Display, Write},
-    ops::{Add, AddAssign, Index, IndexMut, Sub, SubAssign},
+    iter::StepBy,
+    ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    slice,
 };
 
-use crate::util::graph::{Graph, GraphImpl};
+use crate::util::{
+    graph::{Graph, GraphImpl},
+    parsers::ParseError,
+};
+
+pub mod transform;
+pub mod stencil;
+pub mod view;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Pos {
@@ -123,6 +132,119 @@ impl Sub<Pos> for Pos {
     }
 }
 
+/// A position with signed coordinates, for grids without fixed bounds.
+/// Unlike `Pos`, it can go left of or above the origin, and differences never underflow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct IPos {
+    pub x: i64,
+    pub y: i64,
+}
+
+/// An offset between two `IPos`, fx. `b - a`.
+pub type Vec2 = IPos;
+
+pub fn ipos(x: i64, y: i64) -> IPos {
+    IPos { x, y }
+}
+
+impl IPos {
+    pub const ZERO: Self = IPos { x: 0, y: 0 };
+
+    // Manhattan distance to other position.
+    pub fn dist(&self, other: &IPos) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn step(self, dir: Dir4) -> Self {
+        self + dir.vec()
+    }
+
+    pub fn step_n(self, dir: Dir4, n: i64) -> Self {
+        self + dir.vec() * n
+    }
+
+    pub fn step_dir8(self, dir: Dir8) -> Self {
+        self + dir.vec()
+    }
+
+    pub fn swap_xy(self) -> Self {
+        IPos {
+            x: self.y,
+            y: self.x,
+        }
+    }
+
+    /// Smallest coordinates of both positions, the top left corner of their bounding box.
+    pub fn min(self, other: Self) -> Self {
+        ipos(self.x.min(other.x), self.y.min(other.y))
+    }
+
+    /// Largest coordinates of both positions, the bottom right corner of their bounding box.
+    pub fn max(self, other: Self) -> Self {
+        ipos(self.x.max(other.x), self.y.max(other.y))
+    }
+}
+
+impl From<Pos> for IPos {
+    fn from(p: Pos) -> Self {
+        ipos(p.x as i64, p.y as i64)
+    }
+}
+
+impl TryFrom<IPos> for Pos {
+    type Error = std::num::TryFromIntError;
+
+    fn try_from(p: IPos) -> Result<Self, Self::Error> {
+        Ok(pos(p.x.try_into()?, p.y.try_into()?))
+    }
+}
+
+impl AddAssign for IPos {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl Add<IPos> for IPos {
+    type Output = IPos;
+
+    fn add(self, rhs: IPos) -> Self::Output {
+        ipos(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl SubAssign for IPos {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.x -= rhs.x;
+        self.y -= rhs.y;
+    }
+}
+
+impl Sub<IPos> for IPos {
+    type Output = IPos;
+
+    fn sub(self, rhs: IPos) -> Self::Output {
+        ipos(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Mul<i64> for IPos {
+    type Output = IPos;
+
+    fn mul(self, rhs: i64) -> Self::Output {
+        ipos(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for IPos {
+    type Output = IPos;
+
+    fn neg(self) -> Self::Output {
+        ipos(-self.x, -self.y)
+    }
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Rot {
     L,
@@ -191,6 +313,16 @@ impl Dir4 {
         }
     }
 
+    /// Offset of a single step in this direction, with `N` towards negative `y`.
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Dir4::N => ipos(0, -1),
+            Dir4::E => ipos(1, 0),
+            Dir4::S => ipos(0, 1),
+            Dir4::W => ipos(-1, 0),
+        }
+    }
+
     /// Establish an index standard for lookup tables
     pub fn from_idx(idx: usize) -> Option<Self> {
         match idx {
@@ -278,6 +410,20 @@ impl Dir8 {
             Dir8::NW => 7,
         }
     }
+
+    /// Offset of a single step in this direction, with `NO` towards negative `y`.
+    pub fn vec(self) -> Vec2 {
+        match self {
+            Dir8::NO => ipos(0, -1),
+            Dir8::NE => ipos(1, -1),
+            Dir8::EA => ipos(1, 0),
+            Dir8::SE => ipos(1, 1),
+            Dir8::SO => ipos(0, 1),
+            Dir8::SW => ipos(-1, 1),
+            Dir8::WE => ipos(-1, 0),
+            Dir8::NW => ipos(-1, -1),
+        }
+    }
 }
 
 impl std::fmt::Display for Dir8 {
@@ -418,19 +564,9 @@ impl<T: Clone> Grid<T> {
     pub fn col(&self, j: usize) -> Line {
         pos(j, 0).line(&pos(j, self.height - 1)).unwrap()
     }
-}
 
-impl<T: Default + Clone> Grid<T> {
     pub fn crop_area(&self, start: Pos, end: Pos) -> Self {
-        let width = end.x - start.x;
-        let height = end.y - start.y;
-        let mut result = Self::new_filled(width, height, T::default());
-        for x in 0..width {
-            for y in 0..height {
-                result[pos(x, y)] = self[pos(x + start.x, y + start.y)].clone();
-            }
-        }
-        result
+        self.view(start, end).to_grid()
     }
 }
 
@@ -456,6 +592,40 @@ impl<T> Grid<T> {
         }
     }
 
+    /// Like `parse`, but `p` rejects characters by returning `None`,
+    /// which are reported as an invalid `what`, fx. `invalid tile `x``.
+    /// Rows of different lengths are rejected as well.
+    pub fn try_parse<P>(input: &str, what: &str, mut p: P) -> Result<Self, ParseError>
+    where
+        P: FnMut(Pos, char) -> Option<T>,
+    {
+        let mut data = Vec::new();
+        let mut width = None;
+        for (y, line) in input.lines().enumerate() {
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                let text = &line[i..i + c.len_utf8()];
+                let v = p(Pos { x, y }, c)
+                    .ok_or_else(|| ParseError::new(input, text, format!("invalid {what}")))?;
+                data.push(v);
+            }
+            let len = line.chars().count();
+            if *width.get_or_insert(len) != len {
+                let message = format!("expected {} columns, found {len} in", width.unwrap());
+                return Err(ParseError::new(input, line, message));
+            }
+        }
+        let Some(width) = width.filter(|w| *w > 0) else {
+            return Err(ParseError::new(input, &input[..0], "empty grid"));
+        };
+        let height = data.len() / width;
+
+        Ok(Grid {
+            data,
+            width,
+            height,
+        })
+    }
+
     pub fn get(&self, p: Pos) -> Option<&T> {
         if p.x < self.width && p.y < self.height {
             Some(&self.data[p.x + p.y * self.width])
@@ -484,6 +654,48 @@ impl<T> Grid<T> {
         p.x < self.width && p.y < self.height
     }
 
+    /// The cells of row `i`, left to right.
+    pub fn row_cells(&self, i: usize) -> &[T] {
+        &self.data[i * self.width..(i + 1) * self.width]
+    }
+
+    pub fn row_cells_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.data[i * self.width..(i + 1) * self.width]
+    }
+
+    /// The cells of column `j`, top to bottom.
+    pub fn col_cells(&self, j: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(j < self.width, "column {j} out of bounds for width {}", self.width);
+        self.data[j..].iter().step_by(self.width)
+    }
+
+    pub fn col_cells_mut(&mut self, j: usize) -> StepBy<slice::IterMut<'_, T>> {
+        assert!(j < self.width, "column {j} out of bounds for width {}", self.width);
+        self.data[j..].iter_mut().step_by(self.width)
+    }
+
+    /// Every row or column in the order they are crossed when moving in `dir`,
+    /// fx. the columns from bottom to top for `N`.
+    /// Rows are yielded top to bottom and columns left to right,
+    /// and there are none if the grid is empty.
+    pub fn lanes(&self, dir: Dir4) -> impl Iterator<Item = Line> {
+        let (width, height) = (self.width, self.height);
+        let count = match dir {
+            _ if width == 0 || height == 0 => 0,
+            Dir4::N | Dir4::S => width,
+            Dir4::E | Dir4::W => height,
+        };
+        (0..count).map(move |i| {
+            let (start, end) = match dir {
+                Dir4::N => (pos(i, height - 1), pos(i, 0)),
+                Dir4::E => (pos(0, i), pos(width - 1, i)),
+                Dir4::S => (pos(i, 0), pos(i, height - 1)),
+                Dir4::W => (pos(width - 1, i), pos(0, i)),
+            };
+            start.line(&end).unwrap()
+        })
+    }
+
     pub fn find_pos<P>(&self, p: P) -> Option<Pos>
     where
         P: Copy + Fn(&T) -> bool,
@@ -491,7 +703,7 @@ impl<T> Grid<T> {
         for x in 0..self.width {
             for y in 0..self.height {
                 let pos = Pos { x, y };
-                if self.get(pos).map_or(false, p) {
+                if self.get(pos).is_some_and(p) {
                     return Some(pos);
                 }
             }
@@ -691,3 +903,45 @@ impl Display for BitGrid {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn lanes() {
+        let mut grid = Grid::parse("abc\ndef\n", |_, c| c);
+        assert_eq!(grid.row_cells(1), ['d', 'e', 'f']);
+        assert_eq!(grid.col_cells(2).rev().collect_vec(), [&'f', &'c']);
+        grid.col_cells_mut(0).for_each(|c| *c = c.to_ascii_uppercase());
+        grid.row_cells_mut(0)[1] = 'x';
+        assert_eq!(grid.to_string(), "Axc\nDef\n");
+
+        let starts = |dir| grid.lanes(dir).map(|mut lane| lane.next().unwrap()).collect_vec();
+        assert_eq!(starts(Dir4::N), [pos(0, 1), pos(1, 1), pos(2, 1)]);
+        assert_eq!(starts(Dir4::W), [pos(2, 0), pos(2, 1)]);
+        let lane = grid.lanes(Dir4::S).nth(1).unwrap();
+        assert_eq!(lane.map(|p| grid[p]).collect::<String>(), "xe");
+
+        let empty = Grid::new_filled(3, 0, ' ');
+        for dir in [Dir4::N, Dir4::E, Dir4::S, Dir4::W] {
+            assert_eq!(empty.lanes(dir).count(), 0);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 out of bounds for width 3")]
+    fn col_cells_out_of_bounds() {
+        let grid = Grid::parse("abc\ndef\n", |_, c| c);
+        let _ = grid.col_cells(3);
+    }
+
+    #[test]
+    #[should_panic(expected = "column 5 out of bounds for width 3")]
+    fn col_cells_mut_out_of_bounds() {
+        let mut grid = Grid::parse("abc\ndef\n", |_, c| c);
+        let _ = grid.col_cells_mut(5);
+    }
+}
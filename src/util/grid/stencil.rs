@@ -0,0 +1,142 @@
+use std::{ops::Index, slice};
+
+use super::{Grid, IPos, Pos, Vec2};
+use crate::util::graph::GraphImpl;
+
+/// Orthogonal neighbours, in the same order as the grid itself yields them.
+pub const FOUR: &[Vec2] = &[
+    IPos { x: 1, y: 0 },
+    IPos { x: 0, y: 1 },
+    IPos { x: -1, y: 0 },
+    IPos { x: 0, y: -1 },
+];
+
+/// Orthogonal and diagonal neighbours, clockwise from the east.
+pub const EIGHT: &[Vec2] = &[
+    IPos { x: 1, y: 0 },
+    IPos { x: 1, y: 1 },
+    IPos { x: 0, y: 1 },
+    IPos { x: -1, y: 1 },
+    IPos { x: -1, y: 0 },
+    IPos { x: -1, y: -1 },
+    IPos { x: 0, y: -1 },
+    IPos { x: 1, y: -1 },
+];
+
+/// The moves of a chess knight.
+pub const KNIGHT: &[Vec2] = &[
+    IPos { x: 2, y: 1 },
+    IPos { x: 1, y: 2 },
+    IPos { x: -1, y: 2 },
+    IPos { x: -2, y: 1 },
+    IPos { x: -2, y: -1 },
+    IPos { x: -1, y: -2 },
+    IPos { x: 1, y: -2 },
+    IPos { x: 2, y: -1 },
+];
+
+/// A grid whose cells are connected to the cells at the offsets in `stencil`,
+/// so it can be searched like any other graph.
+#[derive(Clone, Copy, Debug)]
+pub struct Connected<'a, T> {
+    grid: &'a Grid<T>,
+    stencil: &'a [Vec2],
+}
+
+impl<T> Grid<T> {
+    /// The grid as a graph with the given neighbourhood, fx. `grid.connected(stencil::EIGHT)`
+    /// to include diagonal steps.
+    pub fn connected<'a>(&'a self, stencil: &'a [Vec2]) -> Connected<'a, T> {
+        Connected {
+            grid: self,
+            stencil,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Connected<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        &self.grid[index]
+    }
+}
+
+impl<'a, T> GraphImpl<T> for Connected<'a, T> {
+    type Node = Pos;
+
+    fn neighbors(&self, node: Pos) -> Self::Neighbors {
+        StencilNeighbors {
+            center: node.into(),
+            width: self.grid.width,
+            height: self.grid.height,
+            offsets: self.stencil.iter(),
+        }
+    }
+    type Neighbors = StencilNeighbors<'a>;
+
+    fn nodes(&self) -> Self::AllNodes {
+        self.grid.nodes()
+    }
+    type AllNodes = std::vec::IntoIter<Pos>;
+
+    fn map<U, F: Copy + FnMut(&T) -> U>(&self, f: F) -> Self::Map<U> {
+        self.grid.map(f)
+    }
+    type Map<U> = Grid<U>;
+}
+
+/// The cells at each offset of a stencil that are within the grid.
+pub struct StencilNeighbors<'a> {
+    center: IPos,
+    width: usize,
+    height: usize,
+    offsets: slice::Iter<'a, Vec2>,
+}
+
+impl Iterator for StencilNeighbors<'_> {
+    type Item = Pos;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.offsets.find_map(|&offset| {
+            let p = Pos::try_from(self.center + offset).ok()?;
+            (p.x < self.width && p.y < self.height).then_some(p)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::util::{grid::pos, pathfinding::bfs};
+
+    #[test]
+    fn stencils() {
+        let grid = Grid::parse("....\n.##.\n....\n", |_, c| c == '#');
+        let plain = grid.neighbors(pos(0, 0)).collect_vec();
+        assert_eq!(
+            grid.connected(FOUR).neighbors(pos(0, 0)).collect_vec(),
+            plain
+        );
+        assert_eq!(
+            grid.connected(EIGHT).neighbors(pos(0, 0)).collect_vec(),
+            [pos(1, 0), pos(1, 1), pos(0, 1)]
+        );
+
+        let distance = |stencil| {
+            let graph = grid.connected(stencil);
+            bfs(&graph, pos(0, 0), |_, n| !graph[n], |_, p| p == pos(3, 2))
+        };
+        assert_eq!(distance(FOUR), Some(5));
+        assert_eq!(distance(EIGHT), Some(4));
+
+        let board = Grid::new_filled(8, 8, ());
+        let knight = board.connected(KNIGHT);
+        assert_eq!(
+            bfs(&knight, pos(0, 0), |_, _| true, |_, p| p == pos(1, 1)),
+            Some(4)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+use std::{
+    fmt::{Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use super::{pos, Dir4, Grid, Pos, Rot};
+
+/// One of the eight ways to rotate and mirror a grid.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Transform {
+    /// Whether rows become columns, applied after the flips.
+    swap_xy: bool,
+    flip_x: bool,
+    flip_y: bool,
+}
+
+impl Transform {
+    pub const IDENTITY: Self = Self::new(false, false, false);
+    /// Mirrors along the main diagonal, so rows become columns.
+    pub const TRANSPOSE: Self = Self::new(true, false, false);
+    /// Mirrors left to right.
+    pub const FLIP_X: Self = Self::new(false, true, false);
+    /// Mirrors top to bottom.
+    pub const FLIP_Y: Self = Self::new(false, false, true);
+
+    const fn new(swap_xy: bool, flip_x: bool, flip_y: bool) -> Self {
+        Self {
+            swap_xy,
+            flip_x,
+            flip_y,
+        }
+    }
+
+    /// A quarter turn, clockwise for `Rot::R`.
+    pub fn rotate(r: Rot) -> Self {
+        match r {
+            Rot::L => Self::new(true, false, true),
+            Rot::R => Self::new(true, true, false),
+        }
+    }
+
+    /// The rotation that makes `dir` point north, fx. a quarter turn to the left for `E`.
+    pub fn facing_north(dir: Dir4) -> Self {
+        match dir {
+            Dir4::N => Self::IDENTITY,
+            Dir4::E => Self::rotate(Rot::L),
+            Dir4::S => Self::FLIP_X.then(Self::FLIP_Y),
+            Dir4::W => Self::rotate(Rot::R),
+        }
+    }
+
+    /// All eight transforms, starting with the identity.
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..8).map(|i| Self::new(i & 4 != 0, i & 1 != 0, i & 2 != 0))
+    }
+
+    /// Applies `self` and then `next`.
+    pub fn then(self, next: Self) -> Self {
+        let (flip_x, flip_y) = if next.swap_xy {
+            (self.flip_y, self.flip_x)
+        } else {
+            (self.flip_x, self.flip_y)
+        };
+        Self::new(
+            self.swap_xy ^ next.swap_xy,
+            flip_x ^ next.flip_x,
+            flip_y ^ next.flip_y,
+        )
+    }
+
+    /// Width and height of a `width` by `height` grid once transformed.
+    pub fn size(self, width: usize, height: usize) -> (usize, usize) {
+        if self.swap_xy {
+            (height, width)
+        } else {
+            (width, height)
+        }
+    }
+
+    /// The position in a `width` by `height` grid that ends up at `p` once transformed,
+    /// or `None` if `p` is outside the transformed grid.
+    pub fn source(self, p: Pos, width: usize, height: usize) -> Option<Pos> {
+        let (w, h) = self.size(width, height);
+        if p.x >= w || p.y >= h {
+            return None;
+        }
+        let x = if self.flip_x { w - 1 - p.x } else { p.x };
+        let y = if self.flip_y { h - 1 - p.y } else { p.y };
+        Some(if self.swap_xy { pos(y, x) } else { pos(x, y) })
+    }
+}
+
+/// A rotated or mirrored grid that reads from the original without copying it.
+#[derive(Clone, Copy, Debug)]
+pub struct Transformed<'a, T> {
+    grid: &'a Grid<T>,
+    transform: Transform,
+}
+
+impl<'a, T> Transformed<'a, T> {
+    pub fn width(&self) -> usize {
+        self.transform.size(self.grid.width, self.grid.height).0
+    }
+
+    pub fn height(&self) -> usize {
+        self.transform.size(self.grid.width, self.grid.height).1
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&'a T> {
+        let src = self
+            .transform
+            .source(p, self.grid.width, self.grid.height)?;
+        self.grid.get(src)
+    }
+
+    /// Transforms the view further, still without copying.
+    pub fn then(self, transform: Transform) -> Self {
+        Self {
+            grid: self.grid,
+            transform: self.transform.then(transform),
+        }
+    }
+
+    pub fn transpose(self) -> Self {
+        self.then(Transform::TRANSPOSE)
+    }
+
+    pub fn rotate(self, r: Rot) -> Self {
+        self.then(Transform::rotate(r))
+    }
+
+    pub fn flip_x(self) -> Self {
+        self.then(Transform::FLIP_X)
+    }
+
+    pub fn flip_y(self) -> Self {
+        self.then(Transform::FLIP_Y)
+    }
+}
+
+impl<T: Clone> Transformed<'_, T> {
+    /// Copies the view to a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        let (width, height) = (self.width(), self.height());
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| self[pos(x, y)].clone()))
+            .collect();
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+}
+
+impl<T> Index<Pos> for Transformed<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T: Display> Display for Transformed<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height() {
+            for x in 0..self.width() {
+                self[pos(x, y)].fmt(f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+/// Like `Transformed`, but writes go through to the original grid.
+#[derive(Debug)]
+pub struct TransformedMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    transform: Transform,
+}
+
+impl<T> TransformedMut<'_, T> {
+    pub fn width(&self) -> usize {
+        self.transform.size(self.grid.width, self.grid.height).0
+    }
+
+    pub fn height(&self) -> usize {
+        self.transform.size(self.grid.width, self.grid.height).1
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        let src = self
+            .transform
+            .source(p, self.grid.width, self.grid.height)?;
+        self.grid.get(src)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        let src = self
+            .transform
+            .source(p, self.grid.width, self.grid.height)?;
+        self.grid.get_mut(src)
+    }
+}
+
+impl<T> Index<Pos> for TransformedMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pos> for TransformedMut<'_, T> {
+    fn index_mut(&mut self, index: Pos) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Grid<T> {
+    /// A view of the grid with `transform` applied, fx. `grid.transformed(Transform::TRANSPOSE)`
+    /// to walk the columns as if they were rows.
+    pub fn transformed(&self, transform: Transform) -> Transformed<'_, T> {
+        Transformed {
+            grid: self,
+            transform,
+        }
+    }
+
+    pub fn transformed_mut(&mut self, transform: Transform) -> TransformedMut<'_, T> {
+        TransformedMut {
+            grid: self,
+            transform,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn transform(&self, transform: Transform) -> Self {
+        self.transformed(transform).to_grid()
+    }
+
+    pub fn transpose(&self) -> Self {
+        self.transform(Transform::TRANSPOSE)
+    }
+
+    /// Turns the grid a quarter turn, clockwise for `Rot::R`.
+    pub fn rotate(&self, r: Rot) -> Self {
+        self.transform(Transform::rotate(r))
+    }
+
+    /// Mirrors the grid left to right.
+    pub fn flip_x(&self) -> Self {
+        self.transform(Transform::FLIP_X)
+    }
+
+    /// Mirrors the grid top to bottom.
+    pub fn flip_y(&self) -> Self {
+        self.transform(Transform::FLIP_Y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abc\ndef\n", |_, c| c)
+    }
+
+    #[test]
+    fn rotates() {
+        let grid = sample();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate(Rot::R).to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate(Rot::L).to_string(), "cf\nbe\nad\n");
+        assert_eq!(grid.flip_x().to_string(), "cba\nfed\n");
+        assert_eq!(grid.flip_y().to_string(), "def\nabc\n");
+        let view = grid.transformed(Transform::rotate(Rot::R));
+        assert_eq!((view.width(), view.height(), view[pos(1, 2)]), (2, 3, 'c'));
+        assert_eq!(view.get(pos(2, 0)), None);
+
+        let mut grid = grid;
+        let mut view = grid.transformed_mut(Transform::facing_north(Dir4::E));
+        view[pos(0, 0)] = 'x';
+        assert_eq!(grid.to_string(), "abx\ndef\n");
+    }
+
+    #[test]
+    fn composes() {
+        let grid = sample();
+        for a in Transform::all() {
+            for b in Transform::all() {
+                let copied = grid.transform(a).transform(b);
+                assert_eq!(grid.transformed(a).then(b).to_grid(), copied);
+                assert_eq!(grid.transform(a.then(b)), copied);
+            }
+        }
+        assert_eq!(Transform::all().map(|t| grid.transform(t)).unique().count(), 8);
+        let turned = (0..4).fold(grid.transformed(Transform::IDENTITY), |v, _| v.rotate(Rot::R));
+        assert_eq!(turned.to_grid(), grid);
+    }
+}
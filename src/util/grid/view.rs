@@ -0,0 +1,358 @@
+use std::{
+    fmt::{Display, Write},
+    ops::{Index, IndexMut},
+};
+
+use super::{pos, Grid, Neighbors, Pos};
+use crate::util::graph::GraphImpl;
+
+/// A rectangle of a grid borrowed without copying, indexed relative to its top left corner.
+#[derive(Clone, Copy, Debug)]
+pub struct GridView<'a, T> {
+    grid: &'a Grid<T>,
+    corner: Pos,
+    width: usize,
+    height: usize,
+}
+
+/// Like `GridView`, but writes go through to the original grid.
+#[derive(Debug)]
+pub struct GridViewMut<'a, T> {
+    grid: &'a mut Grid<T>,
+    corner: Pos,
+    width: usize,
+    height: usize,
+}
+
+/// Width and height of the rectangle from `start` to `end` (exclusive), checked against `grid`.
+fn area<T>(grid: &Grid<T>, start: Pos, end: Pos) -> (usize, usize) {
+    assert!(
+        start.x <= end.x && start.y <= end.y && end.x <= grid.width && end.y <= grid.height,
+        "view {start:?}..{end:?} out of bounds"
+    );
+    (end.x - start.x, end.y - start.y)
+}
+
+/// Top left corners of the `width` by `height` rectangles within `grid_width` by `grid_height`,
+/// `step` apart, row by row.
+fn corners(
+    (grid_width, grid_height): (usize, usize),
+    (width, height): (usize, usize),
+    step: (usize, usize),
+) -> impl Iterator<Item = Pos> {
+    let ys = (0..(grid_height + 1).saturating_sub(height)).step_by(step.1);
+    ys.flat_map(move |y| {
+        let xs = (0..(grid_width + 1).saturating_sub(width)).step_by(step.0);
+        xs.map(move |x| pos(x, y))
+    })
+}
+
+impl<T> Grid<T> {
+    /// The cells from `start` up to, but not including, `end`.
+    pub fn view(&self, start: Pos, end: Pos) -> GridView<'_, T> {
+        let (width, height) = area(self, start, end);
+        GridView {
+            grid: self,
+            corner: start,
+            width,
+            height,
+        }
+    }
+
+    pub fn view_mut(&mut self, start: Pos, end: Pos) -> GridViewMut<'_, T> {
+        let (width, height) = area(self, start, end);
+        GridViewMut {
+            grid: self,
+            corner: start,
+            width,
+            height,
+        }
+    }
+
+    /// Every `width` by `height` rectangle of the grid, fx. the 3x3 neighbourhood of each
+    /// cell that isn't on the border, row by row.
+    pub fn windows(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        assert!(width > 0 && height > 0, "window size must be non-zero");
+        corners((self.width, self.height), (width, height), (1, 1))
+            .map(move |p| self.view(p, pos(p.x + width, p.y + height)))
+    }
+
+    /// Splits the grid into non-overlapping `width` by `height` tiles, row by row.
+    /// If the grid size isn't a multiple of the tile size,
+    /// the tiles along the right and bottom edges are smaller, like `slice::chunks`.
+    pub fn tiles(&self, width: usize, height: usize) -> impl Iterator<Item = GridView<'_, T>> {
+        assert!(width > 0 && height > 0, "window size must be non-zero");
+        let size = (self.width.div_ceil(width) * width, self.height.div_ceil(height) * height);
+        corners(size, (width, height), (width, height)).map(move |p| {
+            let end = pos((p.x + width).min(self.width), (p.y + height).min(self.height));
+            self.view(p, end)
+        })
+    }
+}
+
+impl<'a, T> GridView<'a, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Position of the top left cell in the original grid.
+    pub fn corner(&self) -> Pos {
+        self.corner
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&'a T> {
+        if self.contains(p) {
+            self.grid.get(self.corner + p)
+        } else {
+            None
+        }
+    }
+
+    /// The cells of row `i` of the view, left to right.
+    pub fn row_cells(&self, i: usize) -> &'a [T] {
+        assert!(i < self.height, "row {i} out of bounds for height {}", self.height);
+        let grid = self.grid;
+        &grid.row_cells(self.corner.y + i)[self.corner.x..self.corner.x + self.width]
+    }
+
+    /// A smaller view within this one, from `start` up to, but not including, `end`.
+    pub fn view(&self, start: Pos, end: Pos) -> Self {
+        assert!(end.x <= self.width && end.y <= self.height);
+        self.grid.view(self.corner + start, self.corner + end)
+    }
+}
+
+impl<T: Clone> GridView<'_, T> {
+    /// Copies the view to a new grid.
+    pub fn to_grid(&self) -> Grid<T> {
+        let data = (0..self.height)
+            .flat_map(|i| self.row_cells(i).iter().cloned())
+            .collect();
+        Grid {
+            data,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> GridViewMut<'_, T> {
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Position of the top left cell in the original grid.
+    pub fn corner(&self) -> Pos {
+        self.corner
+    }
+
+    pub fn contains(&self, p: Pos) -> bool {
+        p.x < self.width && p.y < self.height
+    }
+
+    pub fn get(&self, p: Pos) -> Option<&T> {
+        self.as_view().get(p)
+    }
+
+    pub fn get_mut(&mut self, p: Pos) -> Option<&mut T> {
+        if self.contains(p) {
+            self.grid.get_mut(self.corner + p)
+        } else {
+            None
+        }
+    }
+
+    pub fn row_cells_mut(&mut self, i: usize) -> &mut [T] {
+        assert!(i < self.height, "row {i} out of bounds for height {}", self.height);
+        let (start, width) = (self.corner.x, self.width);
+        &mut self.grid.row_cells_mut(self.corner.y + i)[start..start + width]
+    }
+
+    pub fn as_view(&self) -> GridView<'_, T> {
+        GridView {
+            grid: self.grid,
+            corner: self.corner,
+            width: self.width,
+            height: self.height,
+        }
+    }
+}
+
+impl<T> Index<Pos> for GridView<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> Index<Pos> for GridViewMut<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Pos) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pos> for GridViewMut<'_, T> {
+    fn index_mut(&mut self, index: Pos) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<T: Display> Display for GridView<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            for cell in self.row_cells(y) {
+                cell.fmt(f)?;
+            }
+            f.write_char('\n')?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Display> Display for GridViewMut<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.as_view().fmt(f)
+    }
+}
+
+impl<T> GraphImpl<T> for GridView<'_, T> {
+    type Node = Pos;
+
+    fn neighbors(&self, node: Pos) -> Self::Neighbors {
+        Neighbors {
+            center: node,
+            width: self.width,
+            height: self.height,
+            state: 0,
+        }
+    }
+    type Neighbors = Neighbors;
+
+    fn nodes(&self) -> Self::AllNodes {
+        corners((self.width, self.height), (1, 1), (1, 1))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+    type AllNodes = std::vec::IntoIter<Pos>;
+
+    fn map<U, F: FnMut(&T) -> U>(&self, mut f: F) -> Self::Map<U> {
+        let data = (0..self.height)
+            .flat_map(|i| self.row_cells(i).iter())
+            .map(&mut f)
+            .collect();
+        Grid {
+            data,
+            width: self.width,
+            height: self.height,
+        }
+    }
+    type Map<U> = Grid<U>;
+}
+
+impl<T> GraphImpl<T> for GridViewMut<'_, T> {
+    type Node = Pos;
+
+    fn neighbors(&self, node: Pos) -> Self::Neighbors {
+        self.as_view().neighbors(node)
+    }
+    type Neighbors = Neighbors;
+
+    fn nodes(&self) -> Self::AllNodes {
+        self.as_view().nodes()
+    }
+    type AllNodes = std::vec::IntoIter<Pos>;
+
+    fn map<U, F: Copy + FnMut(&T) -> U>(&self, f: F) -> Self::Map<U> {
+        self.as_view().map(f)
+    }
+    type Map<U> = Grid<U>;
+}
+
+#[cfg(test)]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::util::pathfinding::bfs;
+
+    fn sample() -> Grid<char> {
+        Grid::parse("abcd\nefgh\nijkl\n", |_, c| c)
+    }
+
+    #[test]
+    fn views() {
+        let mut grid = sample();
+        let view = grid.view(pos(1, 1), pos(4, 3));
+        assert_eq!(view.to_string(), "fgh\njkl\n");
+        assert_eq!((view[pos(2, 1)], view.get(pos(3, 0))), ('l', None));
+        assert_eq!(view.view(pos(1, 0), pos(3, 2)).to_grid().to_string(), "gh\nkl\n");
+
+        let distance = bfs(&view, pos(0, 0), |_, _| true, |_, p| view[p] == 'l');
+        assert_eq!(distance, Some(3));
+
+        let mut view = grid.view_mut(pos(2, 0), pos(4, 2));
+        view[pos(0, 1)] = 'x';
+        view.row_cells_mut(0).fill('y');
+        assert_eq!(grid.to_string(), "abyy\nefxh\nijkl\n");
+    }
+
+    #[test]
+    fn windows() {
+        let grid = sample();
+        let windows = grid.windows(3, 2).map(|w| w.to_string()).collect_vec();
+        assert_eq!(windows, ["abc\nefg\n", "bcd\nfgh\n", "efg\nijk\n", "fgh\njkl\n"]);
+        assert_eq!(grid.windows(5, 1).count(), 0);
+
+        let tiles = grid.tiles(3, 2).map(|t| (t.corner(), t.to_string())).collect_vec();
+        assert_eq!(
+            tiles,
+            [
+                (pos(0, 0), "abc\nefg\n".to_string()),
+                (pos(3, 0), "d\nh\n".to_string()),
+                (pos(0, 2), "ijk\n".to_string()),
+                (pos(3, 2), "l\n".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn empty_windows() {
+        let _ = sample().windows(0, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "window size must be non-zero")]
+    fn empty_tiles() {
+        let _ = sample().tiles(3, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 out of bounds for height 2")]
+    fn row_cells_out_of_bounds() {
+        let grid = sample();
+        grid.view(pos(1, 0), pos(3, 2)).row_cells(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "row 2 out of bounds for height 2")]
+    fn row_cells_mut_out_of_bounds() {
+        let mut grid = sample();
+        grid.view_mut(pos(1, 0), pos(3, 2)).row_cells_mut(2);
+    }
+}
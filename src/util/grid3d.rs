@@ -83,7 +83,7 @@ impl<T> Grid<T> {
             for y in 0..self.height {
                 for x in 0..self.width {
                     let pos = Pos { x, y, z };
-                    if self.get(pos).map_or(false, pred) {
+                    if self.get(pos).is_some_and(pred) {
                         return Some(pos);
                     }
                 }
@@ -0,0 +1,146 @@
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+};
+
+use fxhash::FxHashMap;
+
+use crate::util::grid::{ipos, pos, Grid, IPos};
+
+/// Side length of the square chunks that the grid grows by.
+const CHUNK: i64 = 32;
+
+/// A grid without bounds, indexed by signed positions.
+/// Cells that were never written hold the default value, and memory is only
+/// allocated for the chunks around written cells, so it can grow in any direction.
+#[derive(Clone, Debug)]
+pub struct InfiniteGrid<T> {
+    chunks: FxHashMap<IPos, Box<[T]>>,
+    default: T,
+    /// Corners of the bounding box of the written cells.
+    bounds: Option<(IPos, IPos)>,
+}
+
+/// Position of the chunk containing `p`, and the index of `p` within that chunk.
+fn locate(p: IPos) -> (IPos, usize) {
+    let chunk = ipos(p.x.div_euclid(CHUNK), p.y.div_euclid(CHUNK));
+    let idx = p.x.rem_euclid(CHUNK) + p.y.rem_euclid(CHUNK) * CHUNK;
+    (chunk, idx as usize)
+}
+
+impl<T: Clone> InfiniteGrid<T> {
+    pub fn new(default: T) -> Self {
+        Self {
+            chunks: FxHashMap::default(),
+            default,
+            bounds: None,
+        }
+    }
+
+    pub fn get(&self, p: IPos) -> &T {
+        let (chunk, idx) = locate(p);
+        self.chunks
+            .get(&chunk)
+            .map_or(&self.default, |cells| &cells[idx])
+    }
+
+    /// Grows the grid to include `p`, which counts as written from then on.
+    pub fn get_mut(&mut self, p: IPos) -> &mut T {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min(p), max.max(p)),
+            None => (p, p),
+        });
+        let (chunk, idx) = locate(p);
+        let default = &self.default;
+        let cells = self
+            .chunks
+            .entry(chunk)
+            .or_insert_with(|| vec![default.clone(); (CHUNK * CHUNK) as usize].into());
+        &mut cells[idx]
+    }
+
+    pub fn set(&mut self, p: IPos, value: T) {
+        *self.get_mut(p) = value;
+    }
+
+    /// Top left and bottom right corners of the written cells, or `None` if there are none.
+    pub fn bounds(&self) -> Option<(IPos, IPos)> {
+        self.bounds
+    }
+
+    /// All cells within the bounds, row by row, including the ones that were never written.
+    pub fn iter(&self) -> impl Iterator<Item = (IPos, &T)> {
+        let (min, max) = self.bounds.unwrap_or((ipos(0, 0), ipos(-1, -1)));
+        (min.y..=max.y)
+            .flat_map(move |y| (min.x..=max.x).map(move |x| (ipos(x, y), self.get(ipos(x, y)))))
+    }
+
+    /// Copies the cells within the bounds to a regular grid.
+    /// Returns the grid and the position of its top left corner,
+    /// so `grid[p]` is `self[corner + IPos::from(p)]`, or `None` if nothing was written.
+    pub fn to_grid(&self) -> Option<(Grid<T>, IPos)> {
+        let (min, max) = self.bounds?;
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Grid::new_filled(width, height, self.default.clone());
+        for (p, value) in self.iter() {
+            let offset = p - min;
+            grid[pos(offset.x as usize, offset.y as usize)] = value.clone();
+        }
+        Some((grid, min))
+    }
+}
+
+impl<T: Clone> Index<IPos> for InfiniteGrid<T> {
+    type Output = T;
+
+    fn index(&self, p: IPos) -> &Self::Output {
+        self.get(p)
+    }
+}
+
+impl<T: Clone> IndexMut<IPos> for InfiniteGrid<T> {
+    fn index_mut(&mut self, p: IPos) -> &mut Self::Output {
+        self.get_mut(p)
+    }
+}
+
+impl<T: Clone + Display> Display for InfiniteGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.to_grid() {
+            Some((grid, _)) => grid.fmt(f),
+            None => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::grid::Dir4;
+
+    #[test]
+    fn grows() {
+        let mut grid = InfiniteGrid::new('.');
+        assert_eq!(grid.bounds(), None);
+        let mut p = ipos(0, 0);
+        for (dir, n) in [(Dir4::W, 40), (Dir4::N, 2), (Dir4::E, 41)] {
+            for _ in 0..n {
+                p = p.step(dir);
+                grid[p] = '#';
+            }
+        }
+        assert_eq!(grid.bounds(), Some((ipos(-40, -2), ipos(1, 0))));
+        assert_eq!(grid[ipos(-40, -1)], '#');
+        assert_eq!(grid[ipos(-39, -1)], '.');
+        assert_eq!(grid[ipos(1000, -1000)], '.');
+
+        let (small, corner) = grid.to_grid().unwrap();
+        assert_eq!(
+            (small.width(), small.height(), corner),
+            (42, 3, ipos(-40, -2))
+        );
+        assert_eq!(small[pos(0, 1)], '#');
+        assert_eq!(grid.iter().filter(|(_, c)| **c == '#').count(), 83);
+    }
+}
@@ -0,0 +1,177 @@
+//! Reads the capital letters that some puzzles draw as their answer.
+//!
+//! There are two fonts: a small one with 4x6 glyphs, and a large one with 6x10 glyphs.
+//! Neither contains the full alphabet, only the letters that have appeared in puzzles.
+
+use crate::util::grid::{pos, BitGrid, Grid};
+
+const SMALL: &[(char, &str)] = &[
+    ('A', ".##. #..# #..# #### #..# #..#"),
+    ('B', "###. #..# ###. #..# #..# ###."),
+    ('C', ".##. #..# #... #... #..# .##."),
+    ('E', "#### #... ###. #... #... ####"),
+    ('F', "#### #... ###. #... #... #..."),
+    ('G', ".##. #..# #... #.## #..# .###"),
+    ('H', "#..# #..# #### #..# #..# #..#"),
+    ('I', "### .#. .#. .#. .#. ###"),
+    ('J', "..## ...# ...# ...# #..# .##."),
+    ('K', "#..# #.#. ##.. #.#. #.#. #..#"),
+    ('L', "#... #... #... #... #... ####"),
+    ('O', ".##. #..# #..# #..# #..# .##."),
+    ('P', "###. #..# #..# ###. #... #..."),
+    ('R', "###. #..# #..# ###. #.#. #..#"),
+    ('S', ".### #... #... .##. ...# ###."),
+    ('U', "#..# #..# #..# #..# #..# .##."),
+    ('Y', "#...# #...# .#.#. ..#.. ..#.. ..#.."),
+    ('Z', "#### ...# ..#. .#.. #... ####"),
+];
+
+const LARGE: &[(char, &str)] = &[
+    ('A', "..##.. .#..#. #....# #....# #....# ###### #....# #....# #....# #....#"),
+    ('B', "#####. #....# #....# #....# #####. #....# #....# #....# #....# #####."),
+    ('C', ".####. #....# #..... #..... #..... #..... #..... #..... #....# .####."),
+    ('E', "###### #..... #..... #..... #####. #..... #..... #..... #..... ######"),
+    ('F', "###### #..... #..... #..... #####. #..... #..... #..... #..... #....."),
+    ('G', ".####. #....# #..... #..... #..... #..### #....# #....# #...## .###.#"),
+    ('H', "#....# #....# #....# #....# ###### #....# #....# #....# #....# #....#"),
+    ('J', "...### ....#. ....#. ....#. ....#. ....#. ....#. #...#. #...#. .###.."),
+    ('K', "#....# #...#. #..#.. #.#... ##.... ##.... #.#... #..#.. #...#. #....#"),
+    ('L', "#..... #..... #..... #..... #..... #..... #..... #..... #..... ######"),
+    ('N', "#....# ##...# ##...# #.#..# #.#..# #..#.# #..#.# #...## #...## #....#"),
+    ('P', "#####. #....# #....# #....# #####. #..... #..... #..... #..... #....."),
+    ('R', "#####. #....# #....# #....# #####. #..#.. #...#. #...#. #....# #....#"),
+    ('X', "#....# #....# .#..#. .#..#. ..##.. ..##.. .#..#. .#..#. #....# #....#"),
+    ('Z', "###### .....# .....# ....#. ...#.. ..#... .#.... #..... #..... ######"),
+];
+
+/// Reads the letters in a picture, or returns `None` if it contains anything else.
+/// Empty rows and columns around the letters are ignored.
+pub fn read(width: usize, height: usize, pixel: impl Fn(usize, usize) -> bool) -> Option<String> {
+    let rows: Vec<usize> = (0..height).filter(|&y| (0..width).any(|x| pixel(x, y))).collect();
+    let (top, bottom) = (*rows.first()?, *rows.last()?);
+    let font = match bottom - top + 1 {
+        6 => SMALL,
+        10 => LARGE,
+        _ => return None,
+    };
+
+    // Letters are separated by empty columns.
+    let filled: Vec<bool> = (0..width)
+        .map(|x| (top..=bottom).any(|y| pixel(x, y)))
+        .collect();
+    let mut res = String::new();
+    let mut x = 0;
+    while x < width {
+        if !filled[x] {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < width && filled[x] {
+            x += 1;
+        }
+        let glyph: Vec<String> = (top..=bottom)
+            .map(|y| {
+                (start..x)
+                    .map(|x| if pixel(x, y) { '#' } else { '.' })
+                    .collect()
+            })
+            .collect();
+        res.push(recognize(font, &glyph.join(" "))?);
+    }
+    Some(res)
+}
+
+pub fn read_grid(grid: &Grid<bool>) -> Option<String> {
+    read(grid.width(), grid.height(), |x, y| grid[pos(x, y)])
+}
+
+pub fn read_bitgrid(grid: &BitGrid) -> Option<String> {
+    read(grid.width(), grid.height(), |x, y| grid.get(pos(x, y)).unwrap())
+}
+
+/// Reads text where `#` marks filled pixels, fx. a rendered grid.
+pub fn read_text(text: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = text
+        .lines()
+        .map(|line| line.chars().map(|c| c == '#' || c == '█').collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max()?;
+    read(width, rows.len(), |x, y| rows[y].get(x).copied().unwrap_or(false))
+}
+
+/// Finds the letter matching `glyph`, which has its empty columns trimmed.
+fn recognize(font: &[(char, &str)], glyph: &str) -> Option<char> {
+    font.iter()
+        .find(|(_, pattern)| trim_columns(pattern) == glyph)
+        .map(|(c, _)| *c)
+}
+
+/// Removes empty columns from the sides of a glyph, since some letters are narrower than the font.
+fn trim_columns(pattern: &str) -> String {
+    let rows: Vec<&str> = pattern.split(' ').collect();
+    let filled = |x: usize| rows.iter().any(|row| row.as_bytes()[x] == b'#');
+    let width = rows[0].len();
+    let start = (0..width).find(|&x| filled(x)).unwrap();
+    let end = (0..width).rfind(|&x| filled(x)).unwrap();
+    let trimmed: Vec<&str> = rows.iter().map(|row| &row[start..=end]).collect();
+    trimmed.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small() {
+        let text = "\
+#..#.####.###...##..
+#..#.#....#..#.#..#.
+####.###..###..#..#.
+#..#.#....#..#.#..#.
+#..#.#....#..#.#..#.
+#..#.####.###...##..
+";
+        assert_eq!(read_text(text).as_deref(), Some("HEBO"));
+        assert_eq!(read_text(&text.replace("####.###", "####.#.#")), None);
+    }
+
+    /// Draws a word in a font, with a gap of two columns between letters.
+    fn render(font: &[(char, &str)], word: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = word
+            .chars()
+            .map(|c| font.iter().find(|(l, _)| *l == c).unwrap().1.split(' ').collect())
+            .collect();
+        let rows = (0..glyphs[0].len()).map(|y| {
+            let row: Vec<&str> = glyphs.iter().map(|glyph| glyph[y]).collect();
+            row.join("..")
+        });
+        rows.collect::<Vec<_>>().join("\n")
+    }
+
+    #[test]
+    fn fonts() {
+        for font in [SMALL, LARGE] {
+            let word: String = font.iter().map(|(c, _)| c).collect();
+            assert_eq!(read_text(&render(font, &word)), Some(word));
+        }
+    }
+
+    #[test]
+    fn grids() {
+        let mut grid = Grid::new_filled(5, 8, false);
+        for (y, row) in ["###", ".#.", ".#.", ".#.", ".#.", "###"].iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                grid[pos(x + 1, y + 1)] = c == '#';
+            }
+        }
+        assert_eq!(read_grid(&grid).as_deref(), Some("I"));
+        let mut bits = BitGrid::new(5, 8, false);
+        for x in 0..5 {
+            for y in 0..8 {
+                bits.set(pos(x, y), grid[pos(x, y)]);
+            }
+        }
+        assert_eq!(read_bitgrid(&bits).as_deref(), Some("I"));
+    }
+}
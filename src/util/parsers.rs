@@ -1,4 +1,84 @@
-use std::{any::TypeId, marker::PhantomData, str::FromStr};
+use std::{any::TypeId, fmt::Display, marker::PhantomData, str::FromStr};
+
+/// Why an input couldn't be parsed, pointing at the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Line of the offending text, starting at 1.
+    pub line: usize,
+    /// Column of the offending text in characters, starting at 1.
+    pub column: usize,
+    /// The offending text, which is empty if something is missing.
+    pub text: String,
+    /// What's wrong with the text, fx. `invalid card`.
+    pub message: String,
+    /// The whole line containing the text.
+    pub source_line: String,
+}
+
+impl ParseError {
+    /// Creates an error about `text`, which must be a slice of `input` so it can be located.
+    pub fn new(input: &str, text: &str, message: impl Into<String>) -> Self {
+        let offset = (text.as_ptr() as usize)
+            .checked_sub(input.as_ptr() as usize)
+            .filter(|offset| offset + text.len() <= input.len())
+            .expect("text should be a slice of the input");
+        let line_start = input[..offset].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = input[offset..].find('\n').map_or(input.len(), |i| offset + i);
+        Self {
+            line: input[..offset].matches('\n').count() + 1,
+            column: input[line_start..offset].chars().count() + 1,
+            text: text.to_owned(),
+            message: message.into(),
+            source_line: input[line_start..line_end].trim_end_matches('\r').to_owned(),
+        }
+    }
+
+    /// Moves an error about `part`, a slice of `input` that was parsed on its own,
+    /// so its line and column are counted from the start of `input` instead.
+    pub fn within(mut self, input: &str, part: &str) -> Self {
+        let outer = Self::new(input, part, "");
+        if self.line == 1 {
+            self.column += outer.column - 1;
+            self.source_line = outer.source_line;
+        }
+        self.line += outer.line - 1;
+        self
+    }
+
+    /// The line containing the error, with the offending text underlined, fx.
+    ///
+    /// ```text
+    /// 3 | 32T3X 765
+    ///   |     ^
+    /// ```
+    pub fn snippet(&self) -> String {
+        let number = self.line.to_string();
+        let margin = " ".repeat(number.len());
+        let indent = " ".repeat(self.column - 1);
+        let underline = "^".repeat(self.text.chars().count().max(1));
+        format!("{number} | {}\n{margin} | {indent}{underline}", self.source_line)
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}, column {}: {}", self.line, self.column, self.message)?;
+        if !self.text.is_empty() {
+            write!(f, " `{}`", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses `text`, a slice of `input`, as the field named `what`, fx. `bid`.
+pub fn parse<T: FromStr>(input: &str, text: &str, what: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| {
+        let problem = if text.is_empty() { "missing" } else { "invalid" };
+        ParseError::new(input, text, format!("{problem} {what}"))
+    })
+}
 
 /// An iterator through the numbers contained in a string.
 /// NOTE: Currently doesn't support negative numbers with zero spaces in-between,
@@ -59,6 +139,26 @@ mod tests {
         assert_eq!(nums, expected);
     }
 
+    #[test]
+    fn errors() {
+        let input = "32T3K 765\nT55J5 684\nKTJJT two\n";
+        let bid = &input[input.rfind("two").unwrap()..][..3];
+        let err = parse::<u32>(input, bid, "bid").unwrap_err();
+        assert_eq!((err.line, err.column), (3, 7));
+        assert_eq!(err.to_string(), "line 3, column 7: invalid bid `two`");
+        assert_eq!(err.snippet(), "3 | KTJJT two\n  |       ^^^");
+
+        let err = parse::<u32>(input, &input[..0], "hand").unwrap_err();
+        assert_eq!(err.to_string(), "line 1, column 1: missing hand");
+
+        let part = &input[input.find("T55").unwrap()..];
+        let err = parse::<u32>(part, &part[3..5], "bid").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+        let err = err.within(input, part);
+        assert_eq!((err.line, err.column), (2, 4));
+        assert_eq!(err.source_line, "T55J5 684");
+    }
+
     #[test]
     fn test1() {
         test("123abca321fwip-321", vec![123, 321, -321]);
@@ -1,4 +1,4 @@
-use crate::util::{graph::Graph, queue::Queue};
+use crate::util::{graph::GraphImpl, queue::Queue};
 use std::mem::swap;
 
 /// Breadth-first search on a graph structure.
@@ -11,7 +11,7 @@ use std::mem::swap;
 /// * `is_target` - checks whether the node is our target.
 ///   This is guaranteed to be called at most once per node,
 ///   allowing this function to be used as a breadth-first traversal.
-pub fn bfs<T, G: Graph<T>>(
+pub fn bfs<T, G: GraphImpl<T>>(
     graph: &G,
     start: G::Node,
     valid_neighbor: impl Fn(G::Node, G::Node) -> bool,
@@ -49,7 +49,7 @@ pub fn dijkstra<T, G, Q>(
     start: G::Node,
 ) -> Option<usize>
 where
-    G: Graph<T>,
+    G: GraphImpl<T>,
     Q: Queue<G::Node, Priority = usize>,
 {
     let mut costs = graph.map(|_| usize::MAX);
@@ -92,7 +92,7 @@ pub fn a_star<T, G, Q>(
     start: G::Node,
 ) -> Option<usize>
 where
-    G: Graph<T>,
+    G: GraphImpl<T>,
     Q: Queue<G::Node, Priority = usize>,
 {
     let mut costs = graph.map(|_| usize::MAX);
@@ -0,0 +1,170 @@
+use std::{
+    fmt::Display,
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::util::{answer::Answer, examples::EXAMPLES_DIR};
+
+/// Directory containing one folder per profile, relative to the crate root.
+pub const DATA_DIR: &str = "data";
+
+/// A set of puzzle inputs, and optionally their answers, stored in a folder like this:
+///
+/// ```text
+/// data/<name>/inputs/day01.txt
+/// data/<name>/answers/day01.txt
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub name: String,
+    pub path: PathBuf,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The data directory doesn't exist.
+    NoDataDirectory(PathBuf),
+    /// The input for a day doesn't exist.
+    MissingInput(PathBuf),
+    /// A profile folder whose name isn't valid UTF-8.
+    InvalidName(PathBuf),
+    /// Any other error while reading a file or directory.
+    Io(PathBuf, io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::NoDataDirectory(path) => write!(f, "no data directory at {}", path.display()),
+            Error::MissingInput(path) => write!(f, "missing input: {}", path.display()),
+            Error::InvalidName(path) => write!(f, "invalid profile name: {}", path.display()),
+            Error::Io(path, e) => write!(f, "couldn't read {}: {e}", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(_, e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Lists the profiles in `data/`, sorted by name.
+pub fn all() -> Result<Vec<Profile>, Error> {
+    in_dir(Path::new(DATA_DIR))
+}
+
+/// Lists the profiles in `dir`, sorted by name.
+/// Files besides folders are ignored, and so is the folder with the puzzle examples.
+pub fn in_dir(dir: &Path) -> Result<Vec<Profile>, Error> {
+    let entries = std::fs::read_dir(dir).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => Error::NoDataDirectory(dir.to_owned()),
+        _ => Error::Io(dir.to_owned(), e),
+    })?;
+    let mut res = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| Error::Io(dir.to_owned(), e))?.path();
+        if !path.is_dir() || path.file_name().is_some_and(|name| name == EXAMPLES_DIR) {
+            continue;
+        }
+        let Some(name) = path.file_name().unwrap().to_str() else {
+            return Err(Error::InvalidName(path));
+        };
+        res.push(Profile {
+            name: name.to_owned(),
+            path,
+        });
+    }
+    res.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(res)
+}
+
+impl Profile {
+    pub fn input_path(&self, day: usize) -> PathBuf {
+        self.path.join("inputs").join(file_name(day))
+    }
+
+    pub fn answers_path(&self, day: usize) -> PathBuf {
+        self.path.join("answers").join(file_name(day))
+    }
+
+    pub fn input(&self, day: usize) -> Result<String, Error> {
+        let path = self.input_path(day);
+        std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::MissingInput(path),
+            _ => Error::Io(path, e),
+        })
+    }
+
+    /// Known answers for a day, with `None` for parts that have no known answer.
+    pub fn answers(&self, day: usize) -> Result<[Option<Answer>; 2], Error> {
+        read_answers(self.answers_path(day))
+    }
+}
+
+/// Reads an answer file, with `None` for both parts if it doesn't exist.
+pub fn read_answers(path: PathBuf) -> Result<[Option<Answer>; 2], Error> {
+    match std::fs::read_to_string(&path) {
+        Ok(text) => Ok(parse_answers(&text)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok([None, None]),
+        Err(e) => Err(Error::Io(path, e)),
+    }
+}
+
+fn file_name(day: usize) -> String {
+    format!("day{day:02}.txt")
+}
+
+/// Parses an answer file, which contains the answer to part 1,
+/// optionally followed by an empty line and the answer to part 2.
+pub fn parse_answers(text: &str) -> [Option<Answer>; 2] {
+    // Blank answers are unknown, so a new day can start out with an empty file.
+    let parse = |text: &str| (!text.trim().is_empty()).then(|| Answer::parse(text));
+    match text.split_once("\n\n") {
+        Some((a, b)) => [parse(a), parse(b)],
+        None => [parse(text), None],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn answers() {
+        let answers = parse_answers("142\n\nDJGF8ASH\n");
+        assert_eq!(answers, [Some(Answer::Int(142)), Some(Answer::from("DJGF8ASH"))]);
+        assert_eq!(parse_answers("50\n"), [Some(Answer::Int(50)), None]);
+        assert_eq!(parse_answers(""), [None, None]);
+    }
+
+    #[test]
+    fn discovery() {
+        let dir = std::env::temp_dir().join(format!("aoc2023-profiles-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert!(matches!(in_dir(&dir), Err(Error::NoDataDirectory(_))));
+
+        for name in ["bob", "alice", EXAMPLES_DIR] {
+            std::fs::create_dir_all(dir.join(name).join("inputs")).unwrap();
+        }
+        std::fs::create_dir_all(dir.join("alice").join("answers")).unwrap();
+        std::fs::write(dir.join("README.md"), "not a profile").unwrap();
+        std::fs::write(dir.join("alice/inputs/day03.txt"), "input").unwrap();
+        std::fs::write(dir.join("alice/answers/day03.txt"), "1\n\n2\n").unwrap();
+
+        let profiles = in_dir(&dir).unwrap();
+        let names: Vec<_> = profiles.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["alice", "bob"]);
+        let alice = &profiles[0];
+        assert_eq!(alice.input(3).unwrap(), "input");
+        assert!(matches!(alice.input(4), Err(Error::MissingInput(_))));
+        assert_eq!(alice.answers(3).unwrap(), [Some(Answer::Int(1)), Some(Answer::Int(2))]);
+        assert_eq!(alice.answers(4).unwrap(), [None, None]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
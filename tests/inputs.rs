@@ -1,94 +1,138 @@
 // We should test each solution against the corresponding data in each profile.
-// The question is if we should have a unique test for each solution,
-// or if some tests should run multiple solutions.
-//
-// The profiles are dynamic, so I guess those can be dynamic.
-// The tests will have to be per-solution.
+// The solutions come from the registry, and the profiles are read from `data/`.
+// There is one test case per day, profile and part, named fx. `day14::alice::part2`,
+// so a single wrong answer shows up as a single failing case, and cases can be filtered by name.
+// The examples in `data/examples/` are tested the same way, named fx. `day10::examples::small::part2`,
+// so every variant is checked against them, not just the one that was written alongside them.
 
-mod inputs {
-    macro_rules! input {
-        ($name:ident, $submod:ident) => {
-            #[test]
-            fn $name() {
-                // TODO: move directory finding to util?
-                let dir = std::fs::read_dir("data").expect("no data directory");
-                let mut profiles: Vec<_> = dir.map(|x| x.unwrap()).collect();
-                profiles.sort_by_key(|profile| profile.path());
-                for entry in profiles {
-                    let profile_name = entry
-                        .path()
-                        .file_name()
-                        .unwrap()
-                        .to_str()
-                        .unwrap()
-                        .to_owned();
-                    let mut input_path = entry.path().to_path_buf();
-                    input_path.push("inputs");
-                    input_path.push(stringify!($name).to_owned() + ".txt");
+use std::sync::{Arc, OnceLock};
 
-                    let mut expected_path = entry.path().to_path_buf();
-                    expected_path.push("answers");
-                    expected_path.push(stringify!($name).to_owned() + ".txt");
-                    println!("trying to read {input_path:?}");
+use aoc2023::{
+    registry::{self, Solution},
+    util::{
+        answer::Answer,
+        examples::{self, Example},
+        profiles::{self, Error, Profile},
+    },
+};
+use libtest_mimic::{Arguments, Failed, Trial};
 
-                    let input =
-                        std::fs::read_to_string(input_path).expect("couldn't read input file");
-                    let (actual1, actual2) = aoc2023::$submod::$name::run(input.as_str());
+/// Both answers of each variant of a day, or why they couldn't be computed.
+type Answers = Result<Vec<(&'static str, [Answer; 2])>, String>;
 
-                    let expected = std::fs::read_to_string(expected_path).ok();
-                    if let Some((expected1, expected2)) =
-                        expected.as_ref().and_then(|x| x.split_once("\n\n"))
-                    {
-                        custom_assert(
-                            &(profile_name.clone() + " part 1"),
-                            &actual1.to_string(),
-                            expected1,
-                        );
-                        custom_assert(&(profile_name + " part 2"), &actual2.to_string(), expected2);
-                    } else if let Some(expected1) = expected {
-                        custom_assert(
-                            &(profile_name + " part 1"),
-                            &actual1.to_string(),
-                            &expected1,
-                        );
-                    }
+/// Where the input and the known answers of a case come from.
+enum Data {
+    Profile(Profile),
+    Example(Example),
+}
+
+impl Data {
+    fn input(&self, day: usize) -> Result<String, Error> {
+        match self {
+            Data::Profile(profile) => profile.input(day),
+            Data::Example(example) => example.input(),
+        }
+    }
+
+    fn answers(&self, day: usize) -> Result<[Option<Answer>; 2], Error> {
+        match self {
+            Data::Profile(profile) => profile.answers(day),
+            Data::Example(example) => example.answers(),
+        }
+    }
+}
+
+/// Answers of each variant of a day, computed once and shared by the cases for both parts.
+struct DayResults {
+    data: Data,
+    day: usize,
+    solutions: Vec<&'static Solution>,
+    answers: OnceLock<Answers>,
+}
+
+impl DayResults {
+    fn answers(&self) -> &Answers {
+        self.answers.get_or_init(|| {
+            let input = self.data.input(self.day).map_err(|e| e.to_string())?;
+            self.solutions
+                .iter()
+                .map(|solution| {
+                    let (a, b) = (solution.run)(&input)
+                        .map_err(|e| format!("{}: invalid input: {e}", solution.variant()))?;
+                    Ok((solution.variant(), [a, b]))
+                })
+                .collect()
+        })
+    }
+
+    /// Checks one part of every variant against the known answer,
+    /// and against the first variant, so they agree even without a known answer.
+    fn check(&self, part: usize) -> Result<(), Failed> {
+        let expected = self.data.answers(self.day).map_err(|e| e.to_string())?[part - 1].clone();
+        let answers = self.answers().as_ref().map_err(|e| e.clone())?;
+        let (reference_variant, reference) = &answers[0];
+        let mut failures = Vec::new();
+        for (variant, answer) in answers {
+            let actual = &answer[part - 1];
+            if let Some(expected) = &expected {
+                if actual != expected {
+                    failures.push(format!(
+                        "{variant}\nexpected: {expected}\n  actual: {actual}"
+                    ));
                 }
+            } else if *actual != reference[part - 1] {
+                failures.push(format!(
+                    "{variant} disagrees with {reference_variant}\n{reference_variant}: {}\n{variant}: {actual}",
+                    reference[part - 1]
+                ));
             }
-        };
+        }
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(failures.join("\n").into())
+        }
     }
+}
 
-    fn custom_assert(profile: &str, actual: &str, expected: &str) {
-        let actual = actual.trim_end();
-        let expected = expected.trim_end();
-        assert!(
-            actual == expected,
-            "wrong result in {profile}\nexpected: {expected}\n  actual: {actual}"
-        );
-    }
+fn main() {
+    let args = Arguments::from_args();
+    let profiles = match profiles::all() {
+        Ok(profiles) => profiles,
+        Err(Error::NoDataDirectory(_)) => {
+            println!("no data directory, skipping");
+            Vec::new()
+        }
+        Err(e) => panic!("{e}"),
+    };
+
+    let examples = examples::all().unwrap_or_else(|e| panic!("{e}"));
 
-    input!(day01, optimized);
-    input!(day02, solutions);
-    input!(day03, solutions);
-    input!(day04, solutions);
-    input!(day05, solutions);
-    input!(day06, solutions);
-    input!(day07, solutions);
-    //input!(day08, solutions);
-    //input!(day09, solutions);
-    //input!(day10, solutions);
-    //input!(day11, solutions);
-    //input!(day12, solutions);
-    //input!(day13, solutions);
-    //input!(day14, solutions);
-    //input!(day15, solutions);
-    //input!(day16, solutions);
-    //input!(day17, solutions);
-    //input!(day18, solutions);
-    //input!(day19, solutions);
-    //input!(day20, solutions);
-    //input!(day21, solutions);
-    //input!(day22, solutions);
-    //input!(day23, solutions);
-    //input!(day24, solutions);
-    //input!(day25, solutions);
+    let mut trials = Vec::new();
+    for (day, solutions) in registry::by_day() {
+        let profiles = profiles
+            .iter()
+            .map(|profile| (profile.name.clone(), Data::Profile(profile.clone())));
+        let examples = examples
+            .iter()
+            .filter(|example| example.day == day)
+            .map(|example| {
+                let name = format!("examples::{}", example.name);
+                (name, Data::Example(example.clone()))
+            });
+        for (name, data) in profiles.chain(examples) {
+            let results = Arc::new(DayResults {
+                data,
+                day,
+                solutions: solutions.clone(),
+                answers: OnceLock::new(),
+            });
+            for part in 1..=2 {
+                let results = results.clone();
+                let name = format!("day{day:02}::{name}::part{part}");
+                trials.push(Trial::test(name, move || results.check(part)));
+            }
+        }
+    }
+    libtest_mimic::run(&args, trials).exit();
 }
@@ -0,0 +1,279 @@
+// Variants of the same day should agree on every valid input, not just the ones in `data/`.
+// Each day with several variants has a generator for random inputs shaped like the real ones,
+// and every variant runs on each generated input.
+// When they disagree, the input is shrunk to a minimal case before the test fails.
+// Set `AOC_FUZZ_SEED` to reproduce a run, and `AOC_FUZZ_CASES` to try more inputs.
+
+use std::fmt::Write;
+
+use aoc2023::{
+    registry::{self, Solution},
+    runner::isolate::{self, Failure},
+    util::answer::Answer,
+};
+
+const DEFAULT_CASES: usize = 500;
+
+/// A random input for a day, kept in a structured form so it can be shrunk.
+trait Input: Sized {
+    fn generate(rng: &mut Rng) -> Self;
+    /// Smaller inputs to try when this one makes the variants disagree, simplest first.
+    fn shrink(&self) -> Vec<Self>;
+    fn render(&self) -> String;
+}
+
+/// Xorshift generator, so runs can be reproduced from a seed without extra dependencies.
+struct Rng(u64);
+
+impl Rng {
+    fn new(seed: u64) -> Self {
+        // Xorshift gets stuck on zero.
+        Self(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A number in `lo..=hi`.
+    fn range(&mut self, lo: u64, hi: u64) -> u64 {
+        lo + self.next() % (hi - lo + 1)
+    }
+
+    fn pick<T: Copy>(&mut self, items: &[T]) -> T {
+        items[self.range(0, items.len() as u64 - 1) as usize]
+    }
+}
+
+fn env(name: &str) -> Option<u64> {
+    let value = std::env::var(name).ok()?;
+    Some(
+        value
+            .parse()
+            .unwrap_or_else(|_| panic!("invalid {name} `{value}`")),
+    )
+}
+
+/// Runs every variant of `day` on random inputs, failing with a minimal input they disagree on.
+fn check<T: Input>(day: usize) {
+    let (_, solutions) = registry::by_day()
+        .into_iter()
+        .find(|(d, _)| *d == day)
+        .expect("day is registered");
+    assert!(solutions.len() > 1, "day {day} has a single variant");
+
+    let seed = env("AOC_FUZZ_SEED").unwrap_or(day as u64);
+    let cases = env("AOC_FUZZ_CASES").map_or(DEFAULT_CASES, |n| n as usize);
+    let mut rng = Rng::new(seed);
+    for _ in 0..cases {
+        let mut input = T::generate(&mut rng);
+        let Some(mut report) = disagreement(&solutions, &input.render()) else {
+            continue;
+        };
+        // Keep taking the first smaller input that still fails, until none do.
+        'shrink: loop {
+            for smaller in input.shrink() {
+                if let Some(smaller_report) = disagreement(&solutions, &smaller.render()) {
+                    (input, report) = (smaller, smaller_report);
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        panic!(
+            "variants disagree (AOC_FUZZ_SEED={seed}) on:\n{}\n{report}",
+            input.render()
+        );
+    }
+}
+
+/// Describes how the variants disagree on `input`, if they do.
+/// Inputs that every variant rejects or panics on are taken to be invalid, so they agree.
+fn disagreement(solutions: &[&'static Solution], input: &str) -> Option<String> {
+    let results: Vec<Result<(Answer, Answer), Failure>> = solutions
+        .iter()
+        .map(|&solution| {
+            let input = input.to_owned();
+            isolate::run(None, move || (solution.run)(&input))
+                .and_then(|res| res.map_err(Failure::InvalidInput))
+        })
+        .collect();
+    let agree = results.iter().all(|res| *res == results[0]);
+    let all_failed = results.iter().all(Result::is_err);
+    if agree || all_failed {
+        return None;
+    }
+    let mut report = String::new();
+    for (solution, res) in solutions.iter().zip(&results) {
+        let _ = match res {
+            Ok((a, b)) => writeln!(report, "{}: {a}, {b}", solution.variant()),
+            Err(failure) => writeln!(report, "{}: {failure}", solution.variant()),
+        };
+    }
+    Some(report)
+}
+
+/// Removes each item of `items` in turn.
+fn without_each<T: Clone>(items: &[T]) -> impl Iterator<Item = Vec<T>> + '_ {
+    (0..items.len()).map(|i| {
+        let mut items = items.to_vec();
+        items.remove(i);
+        items
+    })
+}
+
+/// Lines of calibration values, each with at least one digit.
+#[derive(Clone)]
+struct Day01(Vec<Vec<Token>>);
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Digit(u8),
+    Word(&'static str),
+    Letter(char),
+}
+
+impl Day01 {
+    /// Includes words that aren't digits in the puzzle, and ones that overlap when joined.
+    const WORDS: [&'static str; 12] = [
+        "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+        "nin",
+    ];
+    /// Letters of the number words, so random letters often form (parts of) them.
+    const LETTERS: [char; 15] = [
+        'e', 'f', 'g', 'h', 'i', 'n', 'o', 'r', 's', 't', 'u', 'v', 'w', 'x', 'z',
+    ];
+
+    fn valid(line: &[Token]) -> bool {
+        line.iter().any(|token| matches!(token, Token::Digit(_)))
+    }
+}
+
+impl Input for Day01 {
+    fn generate(rng: &mut Rng) -> Self {
+        let lines = (0..rng.range(1, 5))
+            .map(|_| {
+                let mut line: Vec<Token> = (0..rng.range(0, 8))
+                    .map(|_| match rng.range(0, 2) {
+                        0 => Token::Digit(rng.range(0, 9) as u8),
+                        1 => Token::Word(rng.pick(&Self::WORDS)),
+                        _ => Token::Letter(rng.pick(&Self::LETTERS)),
+                    })
+                    .collect();
+                if !Self::valid(&line) {
+                    let idx = rng.range(0, line.len() as u64) as usize;
+                    line.insert(idx, Token::Digit(rng.range(1, 9) as u8));
+                }
+                line
+            })
+            .collect();
+        Self(lines)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut res: Vec<Self> = if self.0.len() > 1 {
+            without_each(&self.0).map(Self).collect()
+        } else {
+            Vec::new()
+        };
+        for (i, line) in self.0.iter().enumerate() {
+            for smaller in without_each(line).filter(|l| Self::valid(l)) {
+                let mut lines = self.0.clone();
+                lines[i] = smaller;
+                res.push(Self(lines));
+            }
+            // Split words into letters, so the minimal case shows which letters matter.
+            for (j, token) in line.iter().enumerate() {
+                if let Token::Word(word) = token {
+                    let mut lines = self.0.clone();
+                    let letters = word.chars().map(Token::Letter);
+                    lines[i].splice(j..=j, letters);
+                    res.push(Self(lines));
+                }
+            }
+        }
+        res
+    }
+
+    fn render(&self) -> String {
+        let mut res = String::new();
+        for line in &self.0 {
+            for token in line {
+                match token {
+                    Token::Digit(d) => res.push(char::from(b'0' + d)),
+                    Token::Word(w) => res.push_str(w),
+                    Token::Letter(c) => res.push(*c),
+                }
+            }
+            res.push('\n');
+        }
+        res
+    }
+}
+
+/// Races as `(time, record distance)`.
+#[derive(Clone)]
+struct Day06(Vec<(u64, u64)>);
+
+impl Input for Day06 {
+    fn generate(rng: &mut Rng) -> Self {
+        // Part 2 joins the digits of all races, so keep the races few and short
+        // enough for the joined time and distance to fit in a `u64`.
+        let races = (0..rng.range(1, 3))
+            .map(|_| {
+                let time = rng.range(1, 99);
+                // Mostly beatable records, some that can't be beaten.
+                let dist = rng.range(0, time * time / 4 + 2);
+                (time, dist)
+            })
+            .collect();
+        Self(races)
+    }
+
+    fn shrink(&self) -> Vec<Self> {
+        let mut res: Vec<Self> = if self.0.len() > 1 {
+            without_each(&self.0).map(Self).collect()
+        } else {
+            Vec::new()
+        };
+        for (i, &(time, dist)) in self.0.iter().enumerate() {
+            let smaller = [
+                (time / 2, dist),
+                (time - 1, dist),
+                (time, dist / 2),
+                (time, dist.saturating_sub(1)),
+            ];
+            for race in smaller {
+                if race != (time, dist) && race.0 > 0 {
+                    let mut races = self.0.clone();
+                    races[i] = race;
+                    res.push(Self(races));
+                }
+            }
+        }
+        res
+    }
+
+    fn render(&self) -> String {
+        let (mut times, mut dists) = (String::from("Time:    "), String::from("Distance:"));
+        for &(time, dist) in &self.0 {
+            let width = time.to_string().len().max(dist.to_string().len()) + 2;
+            write!(times, "{time:>width$}").unwrap();
+            write!(dists, "{dist:>width$}").unwrap();
+        }
+        format!("{times}\n{dists}\n")
+    }
+}
+
+#[test]
+fn day01() {
+    check::<Day01>(1);
+}
+
+#[test]
+fn day06() {
+    check::<Day06>(6);
+}